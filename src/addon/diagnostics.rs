@@ -0,0 +1,283 @@
+/*!
+
+Diagnostics of the environment the game runs in: wine or Proton version, prefix, host system,
+Steam Deck and gamescope indicators, and the environment variables affecting wine.
+
+The raw values are collected once in a [RawEnvironment] and interpreted by
+[Diagnostics::parse], which is pure so it can be tested with canned inputs.
+
+*/
+
+use std::{collections::BTreeMap, sync::OnceLock};
+
+use crate::addon::wine;
+
+/// Prefixes of the environment variables shown in the diagnostics
+const RELEVANT_VARIABLE_PREFIXES: [&str; 8] = [
+    "WINE",
+    "PROTON_",
+    "DXVK_",
+    "VKD3D_",
+    "STEAM_COMPAT_",
+    "GAMESCOPE",
+    "LUTRIS_",
+    "SteamDeck",
+];
+
+/// Other environment variables shown in the diagnostics
+const RELEVANT_VARIABLES: [&str; 5] = [
+    "SteamAppId",
+    "SteamOS",
+    "XDG_CURRENT_DESKTOP",
+    "XDG_SESSION_TYPE",
+    "LD_PRELOAD",
+];
+
+/// Values collected from wine and the process environment
+#[derive(Debug, Clone, Default)]
+pub struct RawEnvironment {
+    /// Result of `wine_get_version`, `None` on Windows
+    pub wine_version: Option<String>,
+    /// Result of `wine_get_host_version`
+    pub host_version: Option<(String, String)>,
+    pub variables: BTreeMap<String, String>,
+}
+
+impl RawEnvironment {
+    /// Collects the values of the current process
+    pub fn collect() -> Self {
+        Self {
+            wine_version: wine::version(),
+            host_version: wine::host_version(),
+            variables: std::env::vars().collect(),
+        }
+    }
+}
+
+/// Interpreted environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub wine_version: Option<String>,
+    pub proton_version: Option<String>,
+    pub prefix: Option<String>,
+    pub host_os: String,
+    pub steam_deck: bool,
+    pub gamescope: bool,
+    /// Environment variables affecting wine, sorted by name
+    pub variables: Vec<(String, String)>,
+}
+
+impl Diagnostics {
+    pub fn parse(raw: &RawEnvironment) -> Self {
+        let var = |name: &str| {
+            raw.variables
+                .get(name)
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let is_set = |name: &str| var(name).is_some_and(|value| value != "0");
+
+        let host_os = match (&raw.host_version, &raw.wine_version) {
+            (Some((sysname, release)), _) if !release.is_empty() => format!("{sysname} {release}"),
+            (Some((sysname, _)), _) => sysname.clone(),
+            (None, Some(_)) => "Unknown (wine)".to_string(),
+            (None, None) => "Windows".to_string(),
+        };
+
+        let prefix = var("WINEPREFIX").map(str::to_string).or_else(|| {
+            var("STEAM_COMPAT_DATA_PATH").map(|path| format!("{}/pfx", path.trim_end_matches('/')))
+        });
+
+        let proton_version = var("STEAM_COMPAT_TOOL_PATHS")
+            .and_then(proton_from_tool_paths)
+            .or_else(|| {
+                raw.wine_version
+                    .as_deref()
+                    .and_then(proton_from_wine_version)
+            });
+
+        let steam_deck = is_set("SteamDeck")
+            || var("SteamOS").is_some()
+            || raw
+                .host_version
+                .as_ref()
+                .is_some_and(|(_, release)| release.contains("valve"));
+
+        let gamescope = var("GAMESCOPE_WAYLAND_DISPLAY").is_some()
+            || var("XDG_CURRENT_DESKTOP").is_some_and(|desktop| desktop == "gamescope");
+
+        let variables = raw
+            .variables
+            .iter()
+            .filter(|(name, _)| is_relevant_variable(name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        Self {
+            wine_version: raw.wine_version.clone(),
+            proton_version,
+            prefix,
+            host_os,
+            steam_deck,
+            gamescope,
+            variables,
+        }
+    }
+
+    pub fn is_wine(&self) -> bool {
+        self.wine_version.is_some()
+    }
+
+    /// Formats the diagnostics as plain text, to paste in bug reports
+    pub fn report(&self) -> String {
+        let or_none = |value: &Option<String>| value.clone().unwrap_or_else(|| "none".into());
+        let mut report = format!(
+            "Wine: {}\nProton: {}\nPrefix: {}\nHost: {}\nSteam Deck: {}\nGamescope: {}\n",
+            or_none(&self.wine_version),
+            or_none(&self.proton_version),
+            or_none(&self.prefix),
+            self.host_os,
+            if self.steam_deck { "yes" } else { "no" },
+            if self.gamescope { "yes" } else { "no" },
+        );
+        for (name, value) in &self.variables {
+            report.push_str(&format!("{name}={value}\n"));
+        }
+        report
+    }
+}
+
+/// Returns the name of the Proton tool in `STEAM_COMPAT_TOOL_PATHS`, e.g. `Proton 8.0`
+fn proton_from_tool_paths(paths: &str) -> Option<String> {
+    paths
+        .split(':')
+        .filter_map(|path| path.trim_end_matches('/').rsplit('/').next())
+        .find(|name| name.contains("Proton"))
+        .map(str::to_string)
+}
+
+/// Returns the Proton version embedded in some wine version strings, e.g. `9.0 (Proton-9.0-2)`
+fn proton_from_wine_version(version: &str) -> Option<String> {
+    let start = version.find("Proton")?;
+    let proton = version[start..].trim_end_matches(')');
+    Some(proton.to_string())
+}
+
+fn is_relevant_variable(name: &str) -> bool {
+    RELEVANT_VARIABLES.contains(&name)
+        || RELEVANT_VARIABLE_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+static DIAGNOSTICS: OnceLock<Diagnostics> = OnceLock::new();
+
+/// Returns the diagnostics of the current process, collected on first use
+pub fn current() -> &'static Diagnostics {
+    DIAGNOSTICS.get_or_init(|| Diagnostics::parse(&RawEnvironment::collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(
+        wine_version: Option<&str>,
+        host: Option<(&str, &str)>,
+        variables: &[(&str, &str)],
+    ) -> RawEnvironment {
+        RawEnvironment {
+            wine_version: wine_version.map(str::to_string),
+            host_version: host.map(|(sysname, release)| (sysname.into(), release.into())),
+            variables: variables
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn windows() {
+        let diagnostics = Diagnostics::parse(&raw(None, None, &[("PATH", "C:\\Windows")]));
+
+        assert!(!diagnostics.is_wine());
+        assert_eq!(diagnostics.host_os, "Windows");
+        assert_eq!(diagnostics.prefix, None);
+        assert!(diagnostics.variables.is_empty());
+    }
+
+    #[test]
+    fn plain_wine() {
+        let diagnostics = Diagnostics::parse(&raw(
+            Some("9.0"),
+            Some(("Linux", "6.8.0-45-generic")),
+            &[
+                ("WINEPREFIX", "/home/user/Games/gw2"),
+                ("WINEESYNC", "1"),
+                ("HOME", "/home/user"),
+            ],
+        ));
+
+        assert_eq!(diagnostics.wine_version.as_deref(), Some("9.0"));
+        assert_eq!(diagnostics.proton_version, None);
+        assert_eq!(diagnostics.prefix.as_deref(), Some("/home/user/Games/gw2"));
+        assert_eq!(diagnostics.host_os, "Linux 6.8.0-45-generic");
+        assert!(!diagnostics.steam_deck);
+        assert_eq!(
+            diagnostics.variables,
+            vec![
+                ("WINEESYNC".to_string(), "1".to_string()),
+                ("WINEPREFIX".to_string(), "/home/user/Games/gw2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn proton_on_steam_deck() {
+        let diagnostics = Diagnostics::parse(&raw(
+            Some("8.0"),
+            Some(("Linux", "6.1.52-valve16-1-neptune-61")),
+            &[
+                (
+                    "STEAM_COMPAT_TOOL_PATHS",
+                    "/home/deck/.steam/steam/steamapps/common/Proton 8.0:/home/deck/.steam/steam/steamapps/common/SteamLinuxRuntime_sniper",
+                ),
+                (
+                    "STEAM_COMPAT_DATA_PATH",
+                    "/home/deck/.steam/steam/steamapps/compatdata/1284210/",
+                ),
+                ("SteamDeck", "1"),
+                ("XDG_CURRENT_DESKTOP", "gamescope"),
+            ],
+        ));
+
+        assert_eq!(diagnostics.proton_version.as_deref(), Some("Proton 8.0"));
+        assert_eq!(
+            diagnostics.prefix.as_deref(),
+            Some("/home/deck/.steam/steam/steamapps/compatdata/1284210/pfx")
+        );
+        assert!(diagnostics.steam_deck);
+        assert!(diagnostics.gamescope);
+    }
+
+    #[test]
+    fn proton_version_from_wine_version() {
+        let diagnostics = Diagnostics::parse(&raw(Some("9.0 (Proton-9.0-2)"), None, &[]));
+
+        assert_eq!(diagnostics.proton_version.as_deref(), Some("Proton-9.0-2"));
+        assert_eq!(diagnostics.host_os, "Unknown (wine)");
+    }
+
+    #[test]
+    fn empty_and_disabled_values_are_ignored() {
+        let diagnostics = Diagnostics::parse(&raw(
+            Some("9.0"),
+            Some(("Linux", "")),
+            &[("WINEPREFIX", " "), ("SteamDeck", "0")],
+        ));
+
+        assert_eq!(diagnostics.prefix, None);
+        assert!(!diagnostics.steam_deck);
+        assert_eq!(diagnostics.host_os, "Linux");
+    }
+}
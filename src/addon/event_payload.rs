@@ -0,0 +1,353 @@
+/*!
+
+`#[repr(C)]` payloads of the Nexus events other addons use to control the runner.
+
+The layouts are part of the public API and must not change: strings are stored inline as
+nul terminated UTF-8 in fixed size buffers, so the payloads can be copied and contain no
+pointer except the entries of [ExeList].
+
+| Event                      | Direction   | Payload          |
+|----------------------------|-------------|------------------|
+| `EXE_RUNNER_LAUNCH`        | to runner   | [ExeCommand]     |
+| `EXE_RUNNER_STOP`          | to runner   | [ExeCommand]     |
+| `EXE_RUNNER_LIST_REQUEST`  | to runner   | none             |
+| `EXE_RUNNER_LIST`          | from runner | [ExeList]        |
+| `EXE_RUNNER_STATE_CHANGED` | from runner | [ExeStatus]      |
+
+*/
+
+use std::mem::size_of;
+
+/// Capacity of the path buffers, including the nul terminator
+pub const PATH_CAPACITY: usize = 260;
+
+/// Capacity of the name buffers, including the nul terminator
+pub const NAME_CAPACITY: usize = 64;
+
+/// State of an executable, as reported in [ExeStatus::state]
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusCode {
+    /// Not running, either never launched, stopped, or exited successfully
+    Stopped = 0,
+    /// Running
+    Running = 1,
+    /// Running but suspended
+    Suspended = 2,
+    /// Exited by itself with a failure exit code
+    Crashed = 3,
+    /// Running and its readiness probe succeeded, only sent for executables with one
+    Ready = 4,
+    /// Running but its readiness probe did not succeed within the timeout
+    NotReady = 5,
+    /// Not launched, the UAC prompt of an elevated executable was declined
+    LaunchCancelled = 6,
+}
+
+impl StatusCode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(StatusCode::Stopped),
+            1 => Some(StatusCode::Running),
+            2 => Some(StatusCode::Suspended),
+            3 => Some(StatusCode::Crashed),
+            4 => Some(StatusCode::Ready),
+            5 => Some(StatusCode::NotReady),
+            6 => Some(StatusCode::LaunchCancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Executable targeted by an [ExeCommand]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExeTarget {
+    Id(u32),
+    Path(String),
+}
+
+/// Payload of `EXE_RUNNER_LAUNCH` and `EXE_RUNNER_STOP`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExeCommand {
+    /// Id of the executable, 0 to select it by path
+    pub id: u32,
+    /// Path of the executable, used when `id` is 0
+    pub path: [u8; PATH_CAPACITY],
+}
+
+impl ExeCommand {
+    /// Targets the executable with the given id
+    pub fn by_id(id: u32) -> Self {
+        Self {
+            id,
+            path: [0; PATH_CAPACITY],
+        }
+    }
+
+    /// Targets the executable with the given path
+    pub fn by_path(path: &str) -> Result<Self, String> {
+        Ok(Self {
+            id: 0,
+            path: encode_str(path)?,
+        })
+    }
+
+    /// Returns the targeted executable
+    pub fn target(&self) -> Result<ExeTarget, String> {
+        if self.id != 0 {
+            return Ok(ExeTarget::Id(self.id));
+        }
+        let path = decode_str(&self.path)?;
+        if path.is_empty() {
+            return Err("Command targets neither an id nor a path".into());
+        }
+        Ok(ExeTarget::Path(path))
+    }
+}
+
+/// Payload of `EXE_RUNNER_STATE_CHANGED`, and entry of [ExeList]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExeStatus {
+    /// Id of the executable
+    pub id: u32,
+    /// [StatusCode] of the executable
+    pub state: u32,
+    /// Exit code of the process, only meaningful if `has_exit_code` is not 0
+    pub exit_code: i32,
+    /// Whether the process exited with a known exit code
+    pub has_exit_code: u32,
+    /// Path of the executable, truncated to fit
+    pub path: [u8; PATH_CAPACITY],
+    /// Display name of the executable, truncated to fit
+    pub name: [u8; NAME_CAPACITY],
+}
+
+impl ExeStatus {
+    /**
+     * Creates a status payload. Paths and names that don't fit are truncated, the id
+     * identifies the executable.
+     */
+    pub fn new(id: u32, state: StatusCode, exit_code: Option<i32>, path: &str, name: &str) -> Self {
+        Self {
+            id,
+            state: state as u32,
+            exit_code: exit_code.unwrap_or(0),
+            has_exit_code: u32::from(exit_code.is_some()),
+            path: encode_str_truncated(path),
+            name: encode_str_truncated(name),
+        }
+    }
+
+    pub fn state(&self) -> Option<StatusCode> {
+        StatusCode::from_u32(self.state)
+    }
+
+    pub fn exit_code(&self) -> Option<i32> {
+        (self.has_exit_code != 0).then_some(self.exit_code)
+    }
+
+    pub fn path(&self) -> Result<String, String> {
+        decode_str(&self.path)
+    }
+
+    pub fn name(&self) -> Result<String, String> {
+        decode_str(&self.name)
+    }
+}
+
+/**
+ * Payload of `EXE_RUNNER_LIST`, raised in response to `EXE_RUNNER_LIST_REQUEST`.
+ * `entries` points to `count` contiguous [ExeStatus] and is only valid while the
+ * event is being dispatched: subscribers must copy what they need.
+ */
+#[repr(C)]
+#[derive(Debug)]
+pub struct ExeList {
+    pub count: u32,
+    pub entries: *const ExeStatus,
+}
+
+impl ExeList {
+    /// Describes the given entries, which must outlive the returned payload
+    pub fn new(entries: &[ExeStatus]) -> Self {
+        Self {
+            count: u32::try_from(entries.len()).unwrap_or(u32::MAX),
+            entries: entries.as_ptr(),
+        }
+    }
+
+    /**
+     * Returns the entries of the list.
+     *
+     * # Safety
+     * Must only be called while the event carrying the list is being dispatched.
+     */
+    pub unsafe fn entries(&self) -> &[ExeStatus] {
+        if self.entries.is_null() || self.count == 0 {
+            return &[];
+        }
+        // SAFETY: the runner keeps `count` entries alive during the dispatch
+        unsafe { std::slice::from_raw_parts(self.entries, self.count as usize) }
+    }
+}
+
+// The layouts are part of the API, make sure they never change by accident
+const _: () = assert!(size_of::<ExeCommand>() == 4 + PATH_CAPACITY);
+const _: () = assert!(size_of::<ExeStatus>() == 16 + PATH_CAPACITY + NAME_CAPACITY);
+
+/**
+ * Payloads made only of integers and byte arrays, without padding.
+ *
+ * # Safety
+ * Implementors must be `#[repr(C)]`, have no padding bytes, and accept every bit pattern
+ * for each of their fields, like [ExeCommand] and [ExeStatus].
+ */
+pub unsafe trait PlainPayload: Copy + Sized {
+    /// Returns the raw bytes of the payload
+    fn to_bytes(&self) -> Vec<u8> {
+        // SAFETY: plain payloads have no padding, every byte is initialized
+        unsafe { std::slice::from_raw_parts((self as *const Self).cast::<u8>(), size_of::<Self>()) }
+            .to_vec()
+    }
+
+    /// Reads a payload from raw bytes, `None` if their size does not match
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != size_of::<Self>() {
+            return None;
+        }
+        // SAFETY: the size matches and every bit pattern is valid for plain payloads
+        Some(unsafe { std::ptr::read_unaligned(bytes.as_ptr().cast::<Self>()) })
+    }
+}
+
+// SAFETY: integers and byte arrays only, the size assertions above rule out padding
+unsafe impl PlainPayload for ExeCommand {}
+// SAFETY: integers and byte arrays only, the size assertions above rule out padding
+unsafe impl PlainPayload for ExeStatus {}
+
+/// Encodes a string as nul terminated UTF-8, failing if it does not fit
+pub fn encode_str<const N: usize>(value: &str) -> Result<[u8; N], String> {
+    if value.contains('\0') {
+        return Err(format!("String contains a nul byte: {value:?}"));
+    }
+    if value.len() >= N {
+        return Err(format!(
+            "String is {} bytes long, at most {} fit: {value}",
+            value.len(),
+            N - 1
+        ));
+    }
+    let mut buffer = [0; N];
+    buffer[..value.len()].copy_from_slice(value.as_bytes());
+    Ok(buffer)
+}
+
+/// Encodes a string as nul terminated UTF-8, truncated on a character boundary to fit
+pub fn encode_str_truncated<const N: usize>(value: &str) -> [u8; N] {
+    let value = value.split('\0').next().unwrap_or_default();
+    let mut end = value.len().min(N.saturating_sub(1));
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut buffer = [0; N];
+    buffer[..end].copy_from_slice(&value.as_bytes()[..end]);
+    buffer
+}
+
+/// Decodes a nul terminated UTF-8 string
+pub fn decode_str(bytes: &[u8]) -> Result<String, String> {
+    let end = bytes
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or("String is not nul terminated")?;
+    String::from_utf8(bytes[..end].to_vec()).map_err(|e| format!("String is not UTF-8: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_round_trip() {
+        let command = ExeCommand::by_path(r"C:\Tools\Blish HUD\Blish HUD.exe").unwrap();
+        let decoded = ExeCommand::from_bytes(&command.to_bytes()).unwrap();
+
+        assert_eq!(decoded, command);
+        assert_eq!(
+            decoded.target(),
+            Ok(ExeTarget::Path(r"C:\Tools\Blish HUD\Blish HUD.exe".into()))
+        );
+        assert_eq!(
+            ExeCommand::from_bytes(&ExeCommand::by_id(7).to_bytes())
+                .unwrap()
+                .target(),
+            Ok(ExeTarget::Id(7))
+        );
+    }
+
+    #[test]
+    fn status_round_trip() {
+        let status = ExeStatus::new(3, StatusCode::Crashed, Some(-1), "C:/taco.exe", "Taco");
+        let decoded = ExeStatus::from_bytes(&status.to_bytes()).unwrap();
+
+        assert_eq!(decoded, status);
+        assert_eq!(decoded.state(), Some(StatusCode::Crashed));
+        assert_eq!(decoded.exit_code(), Some(-1));
+        assert_eq!(decoded.path().unwrap(), "C:/taco.exe");
+        assert_eq!(decoded.name().unwrap(), "Taco");
+
+        let running = ExeStatus::new(3, StatusCode::Running, None, "C:/taco.exe", "");
+        assert_eq!(running.exit_code(), None);
+    }
+
+    #[test]
+    fn list_exposes_its_entries() {
+        let entries = [
+            ExeStatus::new(1, StatusCode::Running, None, "a.exe", "A"),
+            ExeStatus::new(2, StatusCode::Stopped, Some(0), "b.exe", "B"),
+        ];
+        let list = ExeList::new(&entries);
+        assert_eq!(list.count, 2);
+        assert_eq!(unsafe { list.entries() }, &entries);
+    }
+
+    #[test]
+    fn strings_that_do_not_fit_are_rejected() {
+        let long_path = "a".repeat(PATH_CAPACITY);
+        assert!(ExeCommand::by_path(&long_path).is_err());
+        assert!(ExeCommand::by_path(&long_path[1..]).is_ok());
+        assert!(ExeCommand::by_path("a\0b").is_err());
+    }
+
+    #[test]
+    fn long_paths_are_truncated_in_statuses() {
+        let url = format!("https://example.com/{}", "a".repeat(PATH_CAPACITY));
+        let status = ExeStatus::new(9, StatusCode::Running, None, &url, "Wiki");
+        let decoded = ExeStatus::from_bytes(&status.to_bytes()).unwrap();
+
+        assert_eq!(decoded.id, 9);
+        assert_eq!(decoded.path().unwrap(), url[..PATH_CAPACITY - 1]);
+        assert_eq!(decoded.name().unwrap(), "Wiki");
+    }
+
+    #[test]
+    fn names_are_truncated_on_char_boundaries() {
+        let name = "é".repeat(NAME_CAPACITY);
+        let encoded: [u8; NAME_CAPACITY] = encode_str_truncated(&name);
+        let decoded = decode_str(&encoded).unwrap();
+        assert_eq!(decoded, "é".repeat((NAME_CAPACITY - 1) / 2));
+    }
+
+    #[test]
+    fn malformed_payloads_are_rejected() {
+        assert!(ExeCommand::from_bytes(&[0; 3]).is_none());
+        assert!(ExeCommand::by_id(0).target().is_err());
+        assert!(decode_str(&[b'a'; 4]).is_err());
+        assert!(decode_str(&[0xff, 0]).is_err());
+        assert_eq!(StatusCode::from_u32(4), Some(StatusCode::Ready));
+        assert_eq!(StatusCode::from_u32(6), Some(StatusCode::LaunchCancelled));
+        assert_eq!(StatusCode::from_u32(7), None);
+    }
+}
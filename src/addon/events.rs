@@ -0,0 +1,184 @@
+/*!
+
+Nexus event API letting other addons control the runner.
+
+Other addons raise `EXE_RUNNER_LAUNCH` or `EXE_RUNNER_STOP` with an [ExeCommand] to launch or
+stop an executable, and `EXE_RUNNER_LIST_REQUEST` to receive the list of executables in an
+`EXE_RUNNER_LIST` event. `EXE_RUNNER_STATE_CHANGED` is raised with an [ExeStatus] whenever a
+process starts, stops, crashes, is suspended or resumed, or the UAC prompt of an elevated
+launch is declined. Elevated executables can't be launched by other addons, their UAC prompt
+only shows up when the user asks for a launch.
+
+See [event_payload](crate::addon::event_payload) for the payload layouts.
+
+*/
+
+use std::ffi::c_void;
+
+use nexus::{
+    event::{event_raise, event_subscribe},
+    event_consume,
+};
+
+use crate::addon::{
+    Result,
+    event_payload::{ExeCommand, ExeList, ExeStatus, ExeTarget},
+    manager::{EXE_MANAGER, ExeManager, StateChange},
+};
+
+/// Launches an executable, payload [ExeCommand]
+pub const LAUNCH_EVENT: &str = "EXE_RUNNER_LAUNCH";
+
+/// Stops an executable, payload [ExeCommand]
+pub const STOP_EVENT: &str = "EXE_RUNNER_STOP";
+
+/// Requests an [LIST_EVENT], no payload
+pub const LIST_REQUEST_EVENT: &str = "EXE_RUNNER_LIST_REQUEST";
+
+/// Raised in response to [LIST_REQUEST_EVENT], payload [ExeList]
+pub const LIST_EVENT: &str = "EXE_RUNNER_LIST";
+
+/// Raised when a process changes state, payload [ExeStatus]
+pub const STATE_CHANGED_EVENT: &str = "EXE_RUNNER_STATE_CHANGED";
+
+/// Subscribes to the events raised by other addons
+pub fn setup_event_api() -> Result<()> {
+    event_subscribe(
+        LAUNCH_EVENT,
+        event_consume!(|command: Option<&ExeCommand>| {
+            if let Some(command) = command {
+                on_command(command, true);
+            }
+        }),
+    )
+    .revert_on_unload();
+
+    event_subscribe(
+        STOP_EVENT,
+        event_consume!(|command: Option<&ExeCommand>| {
+            if let Some(command) = command {
+                on_command(command, false);
+            }
+        }),
+    )
+    .revert_on_unload();
+
+    event_subscribe(
+        LIST_REQUEST_EVENT,
+        event_consume!(|_data: Option<&c_void>| {
+            raise_list();
+        }),
+    )
+    .revert_on_unload();
+
+    log::info!("Event API setup successfully");
+    Ok(())
+}
+
+/// Launches or stops the executable targeted by a command
+fn on_command(command: &ExeCommand, launch: bool) {
+    let target = match command.target() {
+        Ok(target) => target,
+        Err(e) => {
+            log::warn!("Ignoring invalid event command: {e}");
+            return;
+        }
+    };
+
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        log::error!("Failed to lock exe manager on event command");
+        return;
+    };
+
+    let path = match &target {
+        ExeTarget::Id(id) => exe_manager
+            .executable_by_id(*id)
+            .map(|exe| exe.path.clone()),
+        ExeTarget::Path(path) => exe_manager
+            .executables()
+            .iter()
+            .find(|exe| exe.path.eq_ignore_ascii_case(path))
+            .map(|exe| exe.path.clone()),
+    };
+    let Some(path) = path else {
+        log::warn!("Event command targets an unknown executable: {target:?}");
+        return;
+    };
+
+    exe_manager.cleanup_finished_processes();
+    let result = if launch {
+        exe_manager.launch_exe_unattended(&path)
+    } else {
+        exe_manager.stop_exe(&path)
+    };
+    if let Err(e) = result {
+        log::warn!("Event command failed: {e}");
+    }
+}
+
+/// Raises the list of executables with their current state
+fn raise_list() {
+    let entries = {
+        let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+            return;
+        };
+        let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+            log::error!("Failed to lock exe manager on list request");
+            return;
+        };
+        exe_manager.cleanup_finished_processes();
+        list_entries(&exe_manager)
+    };
+
+    // The manager is unlocked while raising, subscribers may send commands right away
+    let list = ExeList::new(&entries);
+    // SAFETY: `list` and its entries outlive the synchronous dispatch of the event
+    unsafe { event_raise(LIST_EVENT, (&raw const list).cast::<c_void>()) };
+}
+
+fn list_entries(exe_manager: &ExeManager) -> Vec<ExeStatus> {
+    exe_manager
+        .executables()
+        .iter()
+        .map(|exe| {
+            let state = exe_manager.status_code(&exe.path);
+            ExeStatus::new(exe.id, state, None, &exe.path, exe.display_name())
+        })
+        .collect()
+}
+
+/**
+ * Detects the processes that exited and raises the lifecycle changes recorded
+ * since the last call. Called every frame.
+ */
+pub fn update() {
+    let changes = {
+        let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+            return;
+        };
+        let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+            return;
+        };
+        exe_manager.cleanup_finished_processes();
+        exe_manager.take_state_changes()
+    };
+
+    for change in changes {
+        raise_state_changed(&change);
+    }
+}
+
+fn raise_state_changed(change: &StateChange) {
+    let status = ExeStatus::new(
+        change.id,
+        change.state,
+        change.exit_code,
+        &change.path,
+        &change.name,
+    );
+    // SAFETY: `status` outlives the synchronous dispatch of the event
+    unsafe { event_raise(STATE_CHANGED_EVENT, (&raw const status).cast::<c_void>()) };
+}
@@ -0,0 +1,177 @@
+/*!
+
+Tracks whether the game window has focus and applies the executables' focus policies.
+
+Focus changes are received from the game's window procedure (`WM_ACTIVATEAPP`) and only
+applied once they lasted for the grace period, so quickly alt-tabbing back and forth does
+not stop and restart tools.
+
+*/
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use nexus::wnd_proc::{register_wnd_proc, wnd_proc_callback};
+use serde::{Deserialize, Serialize};
+
+use crate::addon::{Result, manager::EXE_MANAGER};
+
+/// Sent to the game window when the game gets activated or deactivated
+const WM_ACTIVATEAPP: u32 = 0x001C;
+
+/// Time a focus change must last before the policies are applied
+pub const FOCUS_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// What happens to an executable when the game loses or regains focus
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusPolicy {
+    /// Stop the executable when the game loses focus
+    StopOnFocusLoss,
+    /// Launch the executable when the game gains focus
+    StartOnFocusGain,
+    /// Stop the executable when the game loses focus and launch it again on focus gain
+    StopAndRestart,
+    /// Suspend the executable when the game loses focus and resume it on focus gain
+    SuspendAndResume,
+}
+
+impl FocusPolicy {
+    pub const ALL: [FocusPolicy; 4] = [
+        FocusPolicy::StopOnFocusLoss,
+        FocusPolicy::StartOnFocusGain,
+        FocusPolicy::StopAndRestart,
+        FocusPolicy::SuspendAndResume,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FocusPolicy::StopOnFocusLoss => "Stop on focus loss",
+            FocusPolicy::StartOnFocusGain => "Launch on focus gain",
+            FocusPolicy::StopAndRestart => "Stop on focus loss, launch on focus gain",
+            FocusPolicy::SuspendAndResume => "Suspend on focus loss, resume on focus gain",
+        }
+    }
+}
+
+/// Debounces focus changes
+#[derive(Debug)]
+pub struct FocusTracker {
+    focused: bool,
+    changed_at: Option<Instant>,
+    applied: bool,
+}
+
+impl FocusTracker {
+    pub const fn new() -> Self {
+        Self {
+            focused: true,
+            changed_at: None,
+            applied: true,
+        }
+    }
+
+    /// Records a focus change reported by the window
+    pub fn set_focused(&mut self, focused: bool, now: Instant) {
+        if self.focused != focused {
+            self.focused = focused;
+            self.changed_at = Some(now);
+        }
+    }
+
+    /**
+     * Returns the focus state to apply, once a change lasted for the grace period
+     * and differs from the last applied one.
+     */
+    pub fn poll(&mut self, now: Instant) -> Option<bool> {
+        let changed_at = self.changed_at?;
+        if self.focused == self.applied || now.duration_since(changed_at) < FOCUS_GRACE_PERIOD {
+            return None;
+        }
+        self.applied = self.focused;
+        Some(self.focused)
+    }
+}
+
+impl Default for FocusTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+static FOCUS: Mutex<FocusTracker> = Mutex::new(FocusTracker::new());
+
+/// Registers the window procedure callback receiving the focus changes
+pub fn setup_focus_tracking() -> Result<()> {
+    let wnd_proc = wnd_proc_callback!(|_hwnd, msg, wparam, _lparam| {
+        if msg == WM_ACTIVATEAPP {
+            if let Ok(mut focus) = FOCUS.lock() {
+                focus.set_focused(wparam.0 != 0, Instant::now());
+            }
+        }
+        // Never consume the message
+        msg
+    });
+    register_wnd_proc(wnd_proc).revert_on_unload();
+
+    log::info!("Focus tracking setup successfully");
+    Ok(())
+}
+
+/// Applies the focus policies once a focus change lasted for the grace period
+pub fn update() {
+    let Some(focused) = FOCUS
+        .lock()
+        .ok()
+        .and_then(|mut focus| focus.poll(Instant::now()))
+    else {
+        return;
+    };
+    log::info!(
+        "Game window {} focus",
+        if focused { "gained" } else { "lost" }
+    );
+
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        log::error!("Failed to lock exe manager on focus change");
+        return;
+    };
+
+    exe_manager.cleanup_finished_processes();
+    exe_manager.apply_focus_change(focused);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_shorter_than_the_grace_period_are_ignored() {
+        let mut tracker = FocusTracker::new();
+        let start = Instant::now();
+
+        tracker.set_focused(false, start);
+        assert_eq!(tracker.poll(start + Duration::from_millis(500)), None);
+        tracker.set_focused(true, start + Duration::from_secs(1));
+        assert_eq!(tracker.poll(start + Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn lasting_changes_are_applied_once() {
+        let mut tracker = FocusTracker::new();
+        let start = Instant::now();
+
+        tracker.set_focused(false, start);
+        assert_eq!(tracker.poll(start + FOCUS_GRACE_PERIOD), Some(false));
+        assert_eq!(tracker.poll(start + FOCUS_GRACE_PERIOD * 2), None);
+
+        let regained = start + Duration::from_secs(10);
+        tracker.set_focused(true, regained);
+        assert_eq!(tracker.poll(regained + FOCUS_GRACE_PERIOD), Some(true));
+    }
+}
@@ -0,0 +1,216 @@
+/*!
+
+Detection of the transitions between character select, loading screens and in-game.
+
+MumbleLink's `ui_tick` advances every frame while the player is in a map and freezes on
+loading screens and on character select. A short freeze is treated as a loading screen,
+and only a freeze longer than the debounce is considered a return to character select,
+so moving between maps does not stop and restart tools.
+
+This module is pure: time is passed in by the caller.
+
+*/
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Time without a tick after which the player is considered to be on a loading screen
+const LOADING_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Default time without a tick after which the player is considered back on character select
+pub const CHARACTER_SELECT_DEBOUNCE: Duration = Duration::from_secs(20);
+
+/// Transition an executable can be launched or stopped on
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEvent {
+    /// Entered a map from character select (or after the addon was loaded)
+    InGameEntered,
+    /// Went back to character select
+    ReturnedToCharacterSelect,
+}
+
+impl GameEvent {
+    pub const ALL: [GameEvent; 2] = [
+        GameEvent::InGameEntered,
+        GameEvent::ReturnedToCharacterSelect,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameEvent::InGameEntered => "Entering the game",
+            GameEvent::ReturnedToCharacterSelect => "Returning to character select",
+        }
+    }
+}
+
+/// Where the player currently is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameState {
+    CharacterSelect,
+    Loading,
+    InGame,
+}
+
+/// Debounced state machine fed with MumbleLink ticks
+#[derive(Debug)]
+pub struct GameStateTracker {
+    state: GameState,
+    last_tick: Option<u32>,
+    last_tick_change: Option<Instant>,
+    debounce: Duration,
+}
+
+impl GameStateTracker {
+    pub const fn new(debounce: Duration) -> Self {
+        Self {
+            state: GameState::CharacterSelect,
+            last_tick: None,
+            last_tick_change: None,
+            debounce,
+        }
+    }
+
+    pub fn state(&self) -> GameState {
+        self.state
+    }
+
+    /**
+     * Feeds the current MumbleLink tick to the tracker.
+     *
+     * # Arguments
+     * * `ui_tick` - MumbleLink `ui_tick`
+     * * `now` - Current time
+     *
+     * # Returns
+     * The transition that happened, if any.
+     */
+    pub fn update(&mut self, ui_tick: u32, now: Instant) -> Option<GameEvent> {
+        let Some(last_tick) = self.last_tick else {
+            // First observation, nothing to compare against yet
+            self.last_tick = Some(ui_tick);
+            self.last_tick_change = Some(now);
+            return None;
+        };
+
+        if ui_tick != last_tick {
+            self.last_tick = Some(ui_tick);
+            self.last_tick_change = Some(now);
+            let previous = std::mem::replace(&mut self.state, GameState::InGame);
+            return (previous == GameState::CharacterSelect).then_some(GameEvent::InGameEntered);
+        }
+
+        let frozen_for = self.last_tick_change.map_or(Duration::ZERO, |changed| {
+            now.saturating_duration_since(changed)
+        });
+        match self.state {
+            GameState::InGame if frozen_for >= LOADING_THRESHOLD => {
+                self.state = GameState::Loading;
+                None
+            }
+            GameState::Loading if frozen_for >= self.debounce => {
+                self.state = GameState::CharacterSelect;
+                Some(GameEvent::ReturnedToCharacterSelect)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEBOUNCE: Duration = Duration::from_secs(10);
+
+    /// Feeds a tick every 16ms for the given duration, starting at `tick`
+    fn play(
+        tracker: &mut GameStateTracker,
+        start: Instant,
+        tick: &mut u32,
+        duration: Duration,
+    ) -> (Instant, Vec<GameEvent>) {
+        let mut now = start;
+        let mut events = Vec::new();
+        while now < start + duration {
+            now += Duration::from_millis(16);
+            *tick += 1;
+            events.extend(tracker.update(*tick, now));
+        }
+        (now, events)
+    }
+
+    /// Feeds a frozen tick every 16ms for the given duration
+    fn freeze(
+        tracker: &mut GameStateTracker,
+        start: Instant,
+        tick: u32,
+        duration: Duration,
+    ) -> (Instant, Vec<GameEvent>) {
+        let mut now = start;
+        let mut events = Vec::new();
+        while now < start + duration {
+            now += Duration::from_millis(16);
+            events.extend(tracker.update(tick, now));
+        }
+        (now, events)
+    }
+
+    #[test]
+    fn entering_the_game_from_character_select() {
+        let mut tracker = GameStateTracker::new(DEBOUNCE);
+        let mut tick = 100;
+        let start = Instant::now();
+        assert_eq!(tracker.update(tick, start), None);
+
+        let (_, events) = play(&mut tracker, start, &mut tick, Duration::from_secs(1));
+        assert_eq!(events, vec![GameEvent::InGameEntered]);
+        assert_eq!(tracker.state(), GameState::InGame);
+    }
+
+    #[test]
+    fn loading_screens_are_debounced() {
+        let mut tracker = GameStateTracker::new(DEBOUNCE);
+        let mut tick = 0;
+        let start = Instant::now();
+        tracker.update(tick, start);
+        let (now, _) = play(&mut tracker, start, &mut tick, Duration::from_secs(1));
+
+        let (now, events) = freeze(&mut tracker, now, tick, Duration::from_secs(5));
+        assert!(events.is_empty());
+        assert_eq!(tracker.state(), GameState::Loading);
+
+        let (_, events) = play(&mut tracker, now, &mut tick, Duration::from_secs(1));
+        assert!(events.is_empty());
+        assert_eq!(tracker.state(), GameState::InGame);
+    }
+
+    #[test]
+    fn returning_to_character_select_and_back() {
+        let mut tracker = GameStateTracker::new(DEBOUNCE);
+        let mut tick = 0;
+        let start = Instant::now();
+        tracker.update(tick, start);
+        let (now, _) = play(&mut tracker, start, &mut tick, Duration::from_secs(1));
+
+        let (now, events) = freeze(&mut tracker, now, tick, DEBOUNCE + Duration::from_secs(1));
+        assert_eq!(events, vec![GameEvent::ReturnedToCharacterSelect]);
+        assert_eq!(tracker.state(), GameState::CharacterSelect);
+
+        let (_, events) = play(&mut tracker, now, &mut tick, Duration::from_secs(1));
+        assert_eq!(events, vec![GameEvent::InGameEntered]);
+    }
+
+    #[test]
+    fn events_use_snake_case_names() {
+        assert_eq!(
+            serde_json::to_string(&GameEvent::InGameEntered).unwrap(),
+            r#""in_game_entered""#
+        );
+        assert_eq!(
+            serde_json::from_str::<GameEvent>(r#""returned_to_character_select""#).unwrap(),
+            GameEvent::ReturnedToCharacterSelect
+        );
+    }
+}
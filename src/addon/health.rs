@@ -0,0 +1,351 @@
+/*!
+
+Health checks telling whether a running executable still works, not only whether its
+process is alive.
+
+A [HealthCheck] runs one [HealthProbe] every `interval_secs`:
+- a TCP port accepting connections on localhost
+- an HTTP GET on localhost answering `200`
+- a file whose modification time keeps being updated, e.g. a log or heartbeat file
+
+The probes block for at most [PROBE_TIMEOUT] and are run from the monitor thread, which reports
+the results to the manager. The manager keeps the [HealthStatus] and restarts the executable
+after `restart_after` consecutive failures.
+
+*/
+
+use std::{
+    io::{Read, Write},
+    net::{Ipv4Addr, SocketAddr, TcpStream},
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Longest time a probe may take
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// What is checked
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HealthProbe {
+    /// A localhost TCP port accepts connections
+    Tcp { port: u16 },
+    /// A GET of `path` on a localhost port answers `200`
+    Http { port: u16, path: String },
+    /// A file was modified in the last `max_age_secs` seconds
+    FileUpdated { path: String, max_age_secs: u64 },
+}
+
+impl HealthProbe {
+    /**
+     * Runs the probe once.
+     *
+     * # Errors
+     * Returns a description of the failure.
+     */
+    pub fn run(&self) -> Result<(), String> {
+        match self {
+            HealthProbe::Tcp { port } => connect(*port).map(drop),
+            HealthProbe::Http { port, path } => http_get(*port, path),
+            HealthProbe::FileUpdated { path, max_age_secs } => {
+                file_updated(path, Duration::from_secs(*max_age_secs))
+            }
+        }
+    }
+}
+
+/// Periodic health check of an executable
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheck {
+    pub probe: HealthProbe,
+    /// Time between two probes, the first one runs one interval after the launch
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    /// Consecutive failures after which the executable is restarted, never when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_after: Option<u32>,
+}
+
+fn default_interval_secs() -> u64 {
+    10
+}
+
+impl HealthCheck {
+    pub fn new(probe: HealthProbe) -> Self {
+        Self {
+            probe,
+            interval_secs: default_interval_secs(),
+            restart_after: None,
+        }
+    }
+
+    /**
+     * Checks that the health check can be run.
+     *
+     * # Errors
+     * Returns an error describing the first invalid value.
+     */
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs == 0 {
+            return Err("Health check interval must be at least 1 second".into());
+        }
+        if self.restart_after == Some(0) {
+            return Err("Restart after must be at least 1 failure".into());
+        }
+        match &self.probe {
+            HealthProbe::Tcp { port: 0 } | HealthProbe::Http { port: 0, .. } => {
+                Err("Health check port must not be 0".into())
+            }
+            HealthProbe::Http { path, .. } if !path.starts_with('/') => {
+                Err(format!("HTTP health check path must start with /: {path}"))
+            }
+            HealthProbe::FileUpdated { path, .. } if path.trim().is_empty() => {
+                Err("Health check file must not be empty".into())
+            }
+            HealthProbe::FileUpdated {
+                max_age_secs: 0, ..
+            } => Err("Health check file age must be at least 1 second".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Result of the latest probes of a running executable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HealthStatus {
+    Healthy,
+    Unhealthy {
+        /// Consecutive failed probes
+        failures: u32,
+        /// Failure of the latest probe
+        error: String,
+    },
+}
+
+impl HealthStatus {
+    /// Returns the status after a probe, counting the consecutive failures
+    pub fn next(previous: Option<&HealthStatus>, result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => HealthStatus::Healthy,
+            Err(error) => {
+                let failures = match previous {
+                    Some(HealthStatus::Unhealthy { failures, .. }) => failures + 1,
+                    _ => 1,
+                };
+                HealthStatus::Unhealthy { failures, error }
+            }
+        }
+    }
+
+    /// Number of consecutive failures, 0 when healthy
+    pub fn failures(&self) -> u32 {
+        match self {
+            HealthStatus::Healthy => 0,
+            HealthStatus::Unhealthy { failures, .. } => *failures,
+        }
+    }
+}
+
+fn connect(port: u16) -> Result<TcpStream, String> {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    TcpStream::connect_timeout(&address, PROBE_TIMEOUT)
+        .map_err(|e| format!("Port {port} is not accepting connections: {e}"))
+}
+
+fn http_get(port: u16, path: &str) -> Result<(), String> {
+    let mut stream = connect(port)?;
+    let io_error = |e: std::io::Error| format!("HTTP request to port {port} failed: {e}");
+    stream
+        .set_read_timeout(Some(PROBE_TIMEOUT))
+        .map_err(io_error)?;
+    stream
+        .set_write_timeout(Some(PROBE_TIMEOUT))
+        .map_err(io_error)?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.1\r\nHost: localhost:{port}\r\nConnection: close\r\n\r\n"
+    )
+    .map_err(io_error)?;
+
+    // Only the status line is needed, e.g. `HTTP/1.1 200 OK`
+    let mut response = Vec::new();
+    let mut buffer = [0; 256];
+    while !response.contains(&b'\n') {
+        let read = stream.read(&mut buffer).map_err(io_error)?;
+        if read == 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..read]);
+    }
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => Ok(()),
+        Some(status) => Err(format!("GET {path} on port {port} answered {status}")),
+        None => Err(format!("GET {path} on port {port} got no HTTP response")),
+    }
+}
+
+fn file_updated(path: &str, max_age: Duration) -> Result<(), String> {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("Can't read the modification time of {path}: {e}"))?;
+    // A modification time in the future counts as fresh
+    let age = SystemTime::now()
+        .duration_since(modified)
+        .unwrap_or_default();
+    if age <= max_age {
+        Ok(())
+    } else {
+        Err(format!(
+            "{path} was not updated for {}s (at most {}s)",
+            age.as_secs(),
+            max_age.as_secs()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::BufRead, io::BufReader, net::TcpListener, thread};
+
+    /// Serves one HTTP request with the given status line, returning the request line
+    fn http_server(status_line: &'static str) -> (u16, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            // Read the headers until the blank line
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            write!(
+                reader.get_mut(),
+                "{status_line}\r\nContent-Length: 2\r\n\r\nok"
+            )
+            .unwrap();
+            request_line.trim_end().to_string()
+        });
+        (port, server)
+    }
+
+    /// A port nothing listens on
+    fn closed_port() -> u16 {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[test]
+    fn tcp_probe() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert_eq!(HealthProbe::Tcp { port }.run(), Ok(()));
+
+        let port = closed_port();
+        assert!(HealthProbe::Tcp { port }.run().is_err());
+    }
+
+    #[test]
+    fn http_probe_accepts_only_200() {
+        let (port, server) = http_server("HTTP/1.1 200 OK");
+        let probe = HealthProbe::Http {
+            port,
+            path: "/health".into(),
+        };
+        assert_eq!(probe.run(), Ok(()));
+        assert_eq!(server.join().unwrap(), "GET /health HTTP/1.1");
+
+        let (port, server) = http_server("HTTP/1.1 503 Service Unavailable");
+        let error = HealthProbe::Http {
+            port,
+            path: "/".into(),
+        }
+        .run()
+        .unwrap_err();
+        assert!(error.contains("answered 503"), "{error}");
+        server.join().unwrap();
+
+        let port = closed_port();
+        assert!(
+            HealthProbe::Http {
+                port,
+                path: "/".into()
+            }
+            .run()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn file_probe() {
+        let path = std::env::temp_dir().join(format!("exe-runner-health-{}", std::process::id()));
+        std::fs::write(&path, "alive").unwrap();
+        let probe = HealthProbe::FileUpdated {
+            path: path.to_string_lossy().into_owned(),
+            max_age_secs: 60,
+        };
+        assert_eq!(probe.run(), Ok(()));
+
+        let stale = SystemTime::now() - Duration::from_secs(120);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(stale)
+            .unwrap();
+        let error = probe.run().unwrap_err();
+        assert!(error.contains("was not updated"), "{error}");
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(probe.run().is_err());
+    }
+
+    #[test]
+    fn consecutive_failures_are_counted() {
+        let failed = HealthStatus::next(None, Err("down".into()));
+        assert_eq!(failed.failures(), 1);
+        let failed = HealthStatus::next(Some(&failed), Err("still down".into()));
+        assert_eq!(
+            failed,
+            HealthStatus::Unhealthy {
+                failures: 2,
+                error: "still down".into()
+            }
+        );
+        let healthy = HealthStatus::next(Some(&failed), Ok(()));
+        assert_eq!(healthy, HealthStatus::Healthy);
+        assert_eq!(
+            HealthStatus::next(Some(&healthy), Err("down".into())).failures(),
+            1
+        );
+    }
+
+    #[test]
+    fn validation_and_serialization() {
+        let mut check = HealthCheck::new(HealthProbe::Http {
+            port: 8080,
+            path: "health".into(),
+        });
+        assert!(check.validate().is_err());
+        check.probe = HealthProbe::Http {
+            port: 8080,
+            path: "/health".into(),
+        };
+        assert!(check.validate().is_ok());
+        check.restart_after = Some(0);
+        assert!(check.validate().is_err());
+
+        let check: HealthCheck =
+            serde_json::from_str(r#"{"probe":{"type":"tcp","port":5000},"restart_after":3}"#)
+                .unwrap();
+        assert_eq!(check.probe, HealthProbe::Tcp { port: 5000 });
+        assert_eq!(check.interval_secs, 10);
+        assert_eq!(check.restart_after, Some(3));
+    }
+}
@@ -0,0 +1,146 @@
+/*!
+
+Tracks the logged in character and account through Nexus events and automatically switches
+to the profile bound to them.
+
+The character name and profession come from the MumbleLink identity, which Nexus parses and
+raises as `EV_MUMBLE_IDENTITY_UPDATED`. The account name is raised once by arcdps/Nexus as
+`EV_ACCOUNT_NAME`.
+
+*/
+
+use std::{
+    ffi::{CStr, c_char},
+    sync::Mutex,
+};
+
+use nexus::event::{MUMBLE_IDENTITY_UPDATED, MumbleIdentityUpdate, event_consume, event_subscribe};
+
+use crate::addon::{Result, manager::EXE_MANAGER};
+
+/// Identity of the player as last reported by Nexus
+#[derive(Debug, Clone, Default)]
+pub struct CurrentIdentity {
+    pub character: Option<String>,
+    pub profession: u32,
+    pub account: Option<String>,
+}
+
+/// Last known identity, used by the UI to bind the current character
+pub static CURRENT_IDENTITY: Mutex<CurrentIdentity> = Mutex::new(CurrentIdentity {
+    character: None,
+    profession: 0,
+    account: None,
+});
+
+/// Profession names indexed by their MumbleLink id minus one
+pub const PROFESSIONS: [&str; 9] = [
+    "Guardian",
+    "Warrior",
+    "Engineer",
+    "Ranger",
+    "Thief",
+    "Elementalist",
+    "Mesmer",
+    "Necromancer",
+    "Revenant",
+];
+
+/// Subscribes to the identity events
+pub fn setup_identity_tracking() -> Result<()> {
+    MUMBLE_IDENTITY_UPDATED
+        .subscribe(event_consume!(|identity: Option<&MumbleIdentityUpdate>| {
+            if let Some(identity) = identity {
+                let character = c_chars_to_string(&identity.name);
+                on_identity_changed(|current| {
+                    let changed = current.character.as_deref() != Some(character.as_str());
+                    current.character = Some(character);
+                    current.profession = identity.profession;
+                    changed
+                });
+            }
+        }))
+        .revert_on_unload();
+
+    event_subscribe(
+        "EV_ACCOUNT_NAME",
+        event_consume!(|name: Option<&c_char>| {
+            if let Some(name) = name {
+                // SAFETY: the event payload is a null-terminated string
+                let account = unsafe { CStr::from_ptr(name) }.to_string_lossy();
+                let account = account.trim_start_matches(':').to_string();
+                on_identity_changed(|current| {
+                    let changed = current.account.as_deref() != Some(account.as_str());
+                    current.account = Some(account);
+                    changed
+                });
+            }
+        }),
+    )
+    .revert_on_unload();
+
+    log::info!("Identity tracking setup successfully");
+    Ok(())
+}
+
+/// Applies an update to the current identity and switches profile if the update changed it
+fn on_identity_changed(update: impl FnOnce(&mut CurrentIdentity) -> bool) {
+    let identity = {
+        let Ok(mut current) = CURRENT_IDENTITY.lock() else {
+            return;
+        };
+        if !update(&mut current) {
+            return;
+        }
+        current.clone()
+    };
+
+    let Some(character) = identity.character.as_deref() else {
+        return;
+    };
+    log::info!(
+        "Identity changed: {character} ({})",
+        profession_name(identity.profession)
+    );
+
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        log::error!("Failed to lock exe manager on identity change");
+        return;
+    };
+
+    let Some(profile) = exe_manager
+        .profile_for_identity(character, identity.profession, identity.account.as_deref())
+        .map(str::to_string)
+    else {
+        return;
+    };
+
+    if exe_manager.active_profile().map(|p| p.name.as_str()) != Some(profile.as_str()) {
+        log::info!("Automatically switching to profile {profile} for {character}");
+        if let Err(e) = exe_manager.switch_profile(Some(&profile)) {
+            log::error!("Failed to switch profile automatically: {e}");
+        }
+    }
+}
+
+/// Returns the display name of a MumbleLink profession id
+pub fn profession_name(profession: u32) -> &'static str {
+    (profession as usize)
+        .checked_sub(1)
+        .and_then(|index| PROFESSIONS.get(index))
+        .copied()
+        .unwrap_or("Unknown")
+}
+
+/// Reads a null-terminated C string out of a fixed size buffer
+fn c_chars_to_string(chars: &[c_char]) -> String {
+    let bytes: Vec<u8> = chars
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
@@ -10,12 +10,16 @@ use nexus::{
     keybind::register_keybind_with_string,
     keybind_handler,
     paths::get_addon_dir,
-    quick_access::add_quick_access,
+    quick_access::{add_quick_access, add_quick_access_context_menu},
+    render,
     texture::{RawTextureReceiveCallback, load_texture_from_memory},
     texture_receive,
 };
 
-use crate::addon::{NexusError, Result, manager::ExeManager, ui};
+use crate::addon::{
+    NexusError, Result, events, focus, identity, keybinds, manager::ExeManager, monitor,
+    status_block, tick, ui,
+};
 
 /// Nexus addon load function - handles initialization of all nexus-specific functionality
 pub fn load() {
@@ -54,7 +58,15 @@ fn init_addon() -> Result<()> {
     load_addon_textures()?;
     setup_quick_access()?;
     setup_keybinds()?;
+    identity::setup_identity_tracking()?;
+    focus::setup_focus_tracking()?;
+    events::setup_event_api()?;
+    if let Err(e) = status_block::setup_status_block() {
+        log::warn!("Status block unavailable: {e}");
+    }
     ui::setup_main_window_rendering();
+    tick::setup_tick();
+    monitor::start();
 
     // Launch executables that should start on addon load
     let exe_manager_arc =
@@ -69,12 +81,9 @@ fn init_addon() -> Result<()> {
         ))
     })?;
 
-    let paths_to_launch: Vec<String> = exe_manager
-        .executables()
-        .iter()
-        .filter(|exe| exe.launch_on_startup && !exe.is_running)
-        .map(|exe| exe.path.clone())
-        .collect();
+    keybinds::register_all(exe_manager.executables());
+
+    let paths_to_launch = exe_manager.startup_paths();
 
     for path in paths_to_launch {
         if let Err(e) = exe_manager.launch_exe(&path) {
@@ -117,6 +126,15 @@ fn setup_quick_access() -> Result<()> {
     )
     .revert_on_unload();
 
+    add_quick_access_context_menu(
+        "GW2_EXECUTABLE_RUNNER_CONTEXT_MENU",
+        Some("GW2_EXECUTABLE_RUNNER_SHORTCUT"),
+        render!(|ui| {
+            ui::render_quick_access_menu(ui);
+        }),
+    )
+    .revert_on_unload();
+
     log::info!("Quick access menu setup successfully");
     Ok(())
 }
@@ -146,6 +164,8 @@ fn setup_keybinds() -> Result<()> {
 pub fn unload() {
     log::info!("Unloading Gw2 executable runner");
 
+    monitor::stop();
+
     if let Err(e) = (|| -> Result<()> {
         // Stop all running executables before unloading
         if let Some(exe_manager_arc) = crate::addon::manager::EXE_MANAGER.get() {
@@ -165,5 +185,8 @@ pub fn unload() {
         log::error!("Error during gw2 executable runner cleanup: {e}");
     }
 
+    // Elevated launches still waiting for their UAC prompt are stopped once it returns
+    crate::addon::manager::wait_for_elevation_prompts();
+
     log::info!("Gw2 executable runner unloaded");
 }
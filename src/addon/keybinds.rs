@@ -0,0 +1,77 @@
+/*!
+
+Per-executable toggle keybinds registered with Nexus.
+
+Every executable gets a keybind whose identifier is derived from its id, so it survives
+renames and path changes. Pressing it launches the executable when it is stopped and
+stops it otherwise. The key combination is chosen by the user in the Nexus keybind settings,
+the one stored in the executable is only the default.
+
+*/
+
+use nexus::{
+    keybind::{register_keybind_with_string, unregister_keybind},
+    keybind_handler,
+};
+
+use crate::addon::manager::{EXE_MANAGER, Executable};
+
+/// Prefix of the per-executable keybind identifiers, followed by the executable id
+pub const EXE_KEYBIND_PREFIX: &str = "GW2_EXECUTABLE_RUNNER_TOGGLE_";
+
+/// Key combination Nexus uses for a keybind without a default
+const UNBOUND: &str = "(null)";
+
+/// Returns the keybind identifier of an executable
+pub fn keybind_id(exe_id: u32) -> String {
+    format!("{EXE_KEYBIND_PREFIX}{exe_id}")
+}
+
+/// Returns the executable id a keybind identifier was derived from
+fn exe_id_from_keybind(keybind: &str) -> Option<u32> {
+    keybind.strip_prefix(EXE_KEYBIND_PREFIX)?.parse().ok()
+}
+
+/// Registers the toggle keybind of an executable
+pub fn register_exe_keybind(exe: &Executable) {
+    let handler = keybind_handler!(|id, is_release| {
+        if is_release {
+            return;
+        }
+        let Some(exe_id) = exe_id_from_keybind(id) else {
+            log::warn!("Unknown executable keybind: {id}");
+            return;
+        };
+        let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+            return;
+        };
+        match exe_manager_arc.lock() {
+            Ok(mut exe_manager) => {
+                if let Err(e) = exe_manager.toggle_exe(exe_id) {
+                    log::error!("Failed to toggle executable from keybind: {e}");
+                }
+            }
+            Err(e) => log::error!("Failed to lock exe manager on keybind: {e}"),
+        }
+    });
+
+    let default_bind = if exe.keybind.trim().is_empty() {
+        UNBOUND
+    } else {
+        exe.keybind.trim()
+    };
+    register_keybind_with_string(keybind_id(exe.id), handler, default_bind).revert_on_unload();
+}
+
+/// Unregisters the toggle keybind of a removed executable
+pub fn unregister_exe_keybind(exe_id: u32) {
+    unregister_keybind(keybind_id(exe_id));
+}
+
+/// Registers the toggle keybinds of every configured executable
+pub fn register_all(executables: &[Executable]) {
+    for exe in executables {
+        register_exe_keybind(exe);
+    }
+    log::info!("Registered {} executable keybinds", executables.len());
+}
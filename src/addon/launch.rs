@@ -0,0 +1,301 @@
+/*!
+
+Launch strategies, selected by the extension of the launched file.
+
+- Programs are started directly
+- Batch files (`.bat`, `.cmd`) are run with `cmd /c`
+- PowerShell scripts (`.ps1`) are run with `powershell -File`
+- Shortcuts (`.lnk`) are parsed with [shell_link](crate::addon::shell_link) and their target
+  is launched with its own strategy, the shortcut arguments before the executable's ones
+
+Planning a launch is pure apart from reading the shortcut files, which is done through the
+reader passed to [plan_with] so it can be tested.
+
+Elevated launches take a single command line instead of an argument list, built from the plan
+by [LaunchPlan::command_line] with the quoting rules of the standard library.
+
+*/
+
+use std::{fs, io};
+
+use crate::addon::shell_link;
+
+/// Extensions shown in the file dialog
+pub const LAUNCHABLE_EXTENSIONS: [&str; 5] = ["exe", "bat", "cmd", "ps1", "lnk"];
+
+/// How a file is launched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchStrategy {
+    Direct,
+    Batch,
+    PowerShell,
+    Shortcut,
+}
+
+impl LaunchStrategy {
+    /// Selects the strategy from the extension of a path
+    pub fn for_path(path: &str) -> Self {
+        let extension = path
+            .rsplit(['\\', '/'])
+            .next()
+            .and_then(|file_name| file_name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+
+        match extension.as_deref() {
+            Some("bat" | "cmd") => LaunchStrategy::Batch,
+            Some("ps1") => LaunchStrategy::PowerShell,
+            Some("lnk") => LaunchStrategy::Shortcut,
+            _ => LaunchStrategy::Direct,
+        }
+    }
+}
+
+/// Process to spawn for a launch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchPlan {
+    pub program: String,
+    /// Arguments quoted by the standard library
+    pub args: Vec<String>,
+    /// Arguments appended verbatim to the command line
+    pub raw_args: String,
+    /// Working directory from the shortcut, if any
+    pub working_dir: Option<String>,
+}
+
+impl LaunchPlan {
+    /// Joins the arguments into a command line, the quoted ones first then the raw ones
+    pub fn command_line(&self) -> String {
+        self.args
+            .iter()
+            .map(|arg| quote_arg(arg))
+            .chain((!self.raw_args.is_empty()).then(|| self.raw_args.clone()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Quotes an argument so it is parsed back as a single argument by the C runtime
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        match c {
+            '\\' => backslashes += 1,
+            '"' => {
+                // Backslashes before a quote are escaped, and so is the quote
+                quoted.extend(std::iter::repeat_n('\\', backslashes + 1));
+                backslashes = 0;
+            }
+            _ => backslashes = 0,
+        }
+        quoted.push(c);
+    }
+    // Trailing backslashes would escape the closing quote
+    quoted.extend(std::iter::repeat_n('\\', backslashes));
+    quoted.push('"');
+    quoted
+}
+
+/**
+ * Plans the launch of a file, reading shortcuts from the file system.
+ *
+ * # Arguments
+ * * `path` - Path of the launched file
+ * * `args` - Arguments of the executable entry, passed verbatim
+ *
+ * # Errors
+ * Returns an error if a shortcut can't be read or resolved.
+ */
+pub fn plan(path: &str, args: &str) -> Result<LaunchPlan, String> {
+    plan_with(path, args, |path| fs::read(path))
+}
+
+/// Plans the launch of a file, reading shortcuts with `read`
+pub fn plan_with(
+    path: &str,
+    args: &str,
+    read: impl Fn(&str) -> io::Result<Vec<u8>>,
+) -> Result<LaunchPlan, String> {
+    let args = args.trim();
+
+    match LaunchStrategy::for_path(path) {
+        LaunchStrategy::Direct => Ok(LaunchPlan {
+            program: path.to_string(),
+            args: Vec::new(),
+            raw_args: args.to_string(),
+            working_dir: None,
+        }),
+        // `/s` makes cmd strip the outer quotes only, keeping the quoted script path intact
+        LaunchStrategy::Batch => Ok(LaunchPlan {
+            program: "cmd.exe".to_string(),
+            args: vec!["/d".into(), "/s".into(), "/c".into()],
+            raw_args: format!(
+                "\"\"{path}\"{}{args}\"",
+                if args.is_empty() { "" } else { " " }
+            ),
+            working_dir: None,
+        }),
+        LaunchStrategy::PowerShell => Ok(LaunchPlan {
+            program: "powershell.exe".to_string(),
+            args: vec![
+                "-NoProfile".into(),
+                "-ExecutionPolicy".into(),
+                "Bypass".into(),
+                "-File".into(),
+                path.to_string(),
+            ],
+            raw_args: args.to_string(),
+            working_dir: None,
+        }),
+        LaunchStrategy::Shortcut => {
+            let data = read(path).map_err(|e| format!("Failed to read shortcut {path}: {e}"))?;
+            let link = shell_link::parse(&data).map_err(|e| format!("{e}: {path}"))?;
+
+            let link_dir = path.rsplit_once(['\\', '/']).map_or("", |(dir, _)| dir);
+            let target = link
+                .target(link_dir)
+                .ok_or_else(|| format!("Shortcut target can't be resolved: {path}"))?;
+            if LaunchStrategy::for_path(&target) == LaunchStrategy::Shortcut {
+                return Err(format!("Shortcut points to another shortcut: {path}"));
+            }
+
+            let link_args = link.arguments.as_deref().unwrap_or_default().trim();
+            let combined_args = [link_args, args]
+                .into_iter()
+                .filter(|args| !args.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let mut plan = plan_with(&target, &combined_args, read)?;
+            plan.working_dir = link.working_dir.filter(|dir| !dir.trim().is_empty());
+            Ok(plan)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal unicode shell link with a relative target, working directory and arguments
+    fn shortcut(relative_path: &str, working_dir: &str, arguments: &str) -> Vec<u8> {
+        let mut data = vec![0; 0x4C];
+        data[..4].copy_from_slice(&0x4Cu32.to_le_bytes());
+        data[4..20].copy_from_slice(&[
+            0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x46,
+        ]);
+        // HasRelativePath | HasWorkingDir | HasArguments | IsUnicode
+        data[20..24].copy_from_slice(&0xB8u32.to_le_bytes());
+        for value in [relative_path, working_dir, arguments] {
+            let units: Vec<u16> = value.encode_utf16().collect();
+            data.extend_from_slice(&(units.len() as u16).to_le_bytes());
+            for unit in units {
+                data.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+        data
+    }
+
+    fn no_files(path: &str) -> io::Result<Vec<u8>> {
+        Err(io::Error::new(io::ErrorKind::NotFound, path.to_string()))
+    }
+
+    #[test]
+    fn strategy_from_extension() {
+        assert_eq!(
+            LaunchStrategy::for_path(r"C:\a\tool.exe"),
+            LaunchStrategy::Direct
+        );
+        assert_eq!(
+            LaunchStrategy::for_path(r"C:\a\run.BAT"),
+            LaunchStrategy::Batch
+        );
+        assert_eq!(
+            LaunchStrategy::for_path("C:/a/run.cmd"),
+            LaunchStrategy::Batch
+        );
+        assert_eq!(
+            LaunchStrategy::for_path(r"C:\a\run.ps1"),
+            LaunchStrategy::PowerShell
+        );
+        assert_eq!(
+            LaunchStrategy::for_path(r"C:\a\Tool.lnk"),
+            LaunchStrategy::Shortcut
+        );
+        assert_eq!(
+            LaunchStrategy::for_path(r"C:\a.lnk\tool"),
+            LaunchStrategy::Direct
+        );
+    }
+
+    #[test]
+    fn scripts_run_through_their_interpreter() {
+        let batch = plan_with(r"C:\My Tools\run.bat", "--fast", no_files).unwrap();
+        assert_eq!(batch.program, "cmd.exe");
+        assert_eq!(batch.args, ["/d", "/s", "/c"]);
+        assert_eq!(batch.raw_args, r#"""C:\My Tools\run.bat" --fast""#);
+
+        let no_args = plan_with(r"C:\run.cmd", "", no_files).unwrap();
+        assert_eq!(no_args.raw_args, r#"""C:\run.cmd"""#);
+
+        let powershell = plan_with(r"C:\run.ps1", "-Verbose", no_files).unwrap();
+        assert_eq!(powershell.program, "powershell.exe");
+        assert_eq!(
+            powershell.args.last().map(String::as_str),
+            Some(r"C:\run.ps1")
+        );
+        assert_eq!(powershell.raw_args, "-Verbose");
+    }
+
+    #[test]
+    fn shortcuts_launch_their_target() {
+        let read = |path: &str| {
+            assert_eq!(path, r"C:\Links\Taco.lnk");
+            Ok(shortcut(r".\taco\start.bat", r"C:\Links\taco", "--overlay"))
+        };
+
+        let plan = plan_with(r"C:\Links\Taco.lnk", "--debug", read).unwrap();
+        assert_eq!(plan.program, "cmd.exe");
+        assert_eq!(
+            plan.raw_args,
+            r#"""C:\Links\taco\start.bat" --overlay --debug""#
+        );
+        assert_eq!(plan.working_dir.as_deref(), Some(r"C:\Links\taco"));
+    }
+
+    #[test]
+    fn command_line_quotes_the_arguments() {
+        let powershell = plan_with(r"C:\My Tools\run.ps1", "-Verbose", no_files).unwrap();
+        assert_eq!(
+            powershell.command_line(),
+            r#"-NoProfile -ExecutionPolicy Bypass -File "C:\My Tools\run.ps1" -Verbose"#
+        );
+
+        let direct = plan_with(r"C:\tool.exe", "", no_files).unwrap();
+        assert_eq!(direct.command_line(), "");
+
+        assert_eq!(quote_arg(""), r#""""#);
+        assert_eq!(quote_arg(r"C:\dir\"), r"C:\dir\");
+        assert_eq!(quote_arg(r"C:\my dir\"), r#""C:\my dir\\""#);
+        assert_eq!(quote_arg(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quote_arg(r#"a\"b"#), r#""a\\\"b""#);
+    }
+
+    #[test]
+    fn shortcut_errors() {
+        assert!(plan_with(r"C:\missing.lnk", "", no_files).is_err());
+        assert!(plan_with(r"C:\bad.lnk", "", |_: &str| Ok(vec![0; 8])).is_err());
+        assert!(
+            plan_with(r"C:\loop.lnk", "", |_: &str| Ok(shortcut(
+                "other.lnk",
+                "",
+                ""
+            )))
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,371 @@
+/*!
+
+Soft resource limits of an executable, e.g. "restart if the working set stays above 1.5 GB
+for 60s".
+
+The [monitor](crate::addon::monitor) feeds every usage sample to the [LimitTracker] of the
+process, which reports a [Breach] once a limit has been exceeded for the whole grace duration.
+The tracker only reports a breach once per episode: usage has to go back under the limits
+before the same limit is reported again.
+
+A [RestartBudget] caps the restarts the runner does on its own, so a program breaching its
+limits right after every launch is stopped instead of restarted in a loop.
+
+*/
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::addon::usage::{Sample, format_bytes};
+
+/// What the runner does when a limit is breached
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitAction {
+    /// Log a warning and add it to the run history
+    #[default]
+    Warn,
+    /// Stop the process and launch it again
+    Restart,
+    /// Stop the process
+    Stop,
+}
+
+impl LimitAction {
+    pub const ALL: [LimitAction; 3] = [LimitAction::Warn, LimitAction::Restart, LimitAction::Stop];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LimitAction::Warn => "Warn",
+            LimitAction::Restart => "Restart",
+            LimitAction::Stop => "Stop",
+        }
+    }
+}
+
+/// Soft limits on the usage of a running executable
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ResourceLimits {
+    /// Working set limit in megabytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_mb: Option<u64>,
+    /// CPU limit, as a share of the whole machine like the displayed usage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_percent: Option<f32>,
+    /// How long a limit must be exceeded before acting
+    #[serde(default = "default_duration_secs")]
+    pub duration_secs: u64,
+    #[serde(default)]
+    pub action: LimitAction,
+}
+
+fn default_duration_secs() -> u64 {
+    60
+}
+
+/// Shortest grace duration, a single sample above the limits is not a breach
+pub const MIN_DURATION_SECS: u64 = 5;
+
+/// Restarts the runner does for one executable within [RESTART_WINDOW]
+pub const MAX_RESTARTS: usize = 3;
+
+/// Window in which restarts count against [MAX_RESTARTS]
+pub const RESTART_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            max_memory_mb: None,
+            max_cpu_percent: None,
+            duration_secs: default_duration_secs(),
+            action: LimitAction::default(),
+        }
+    }
+}
+
+impl ResourceLimits {
+    /// Checks whether no limit is set
+    pub fn is_empty(&self) -> bool {
+        self.max_memory_mb.is_none() && self.max_cpu_percent.is_none()
+    }
+
+    /**
+     * Checks that the limits can be enforced.
+     *
+     * # Errors
+     * Returns an error describing the first invalid value.
+     */
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_memory_mb == Some(0) {
+            return Err("Memory limit must be greater than 0 MB".into());
+        }
+        if self
+            .max_cpu_percent
+            .is_some_and(|cpu| !(cpu > 0.0 && cpu <= 100.0))
+        {
+            return Err("CPU limit must be between 0 and 100%".into());
+        }
+        if self.duration_secs < MIN_DURATION_SECS {
+            return Err(format!(
+                "Limits must be exceeded for at least {MIN_DURATION_SECS}s"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the first limit exceeded by a sample
+    fn exceeded_by(&self, sample: &Sample) -> Option<Resource> {
+        let memory_limit = self.max_memory_mb.map(|mb| mb * 1024 * 1024);
+        if memory_limit.is_some_and(|limit| sample.working_set > limit) {
+            return Some(Resource::Memory);
+        }
+        if self
+            .max_cpu_percent
+            .is_some_and(|limit| sample.cpu_percent > limit)
+        {
+            return Some(Resource::Cpu);
+        }
+        None
+    }
+}
+
+/// Resource whose limit was exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Memory,
+    Cpu,
+}
+
+/// A limit exceeded for the whole grace duration
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breach {
+    pub resource: Resource,
+    /// Usage of the sample that completed the breach
+    pub sample: Sample,
+    pub limits: ResourceLimits,
+}
+
+impl Breach {
+    /// Describes the breach for the log and the run history
+    pub fn describe(&self) -> String {
+        let duration = self.limits.duration_secs;
+        match self.resource {
+            Resource::Memory => format!(
+                "working set {} above the {} limit for {duration}s",
+                format_bytes(self.sample.working_set),
+                format_bytes(self.limits.max_memory_mb.unwrap_or_default() * 1024 * 1024)
+            ),
+            Resource::Cpu => format!(
+                "CPU {:.1}% above the {:.1}% limit for {duration}s",
+                self.sample.cpu_percent,
+                self.limits.max_cpu_percent.unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Tracks how long the limits of one process have been exceeded
+#[derive(Debug, Clone, Default)]
+pub struct LimitTracker {
+    exceeded: Option<(Resource, Instant)>,
+    reported: bool,
+}
+
+impl LimitTracker {
+    /**
+     * Updates the tracker with a new sample.
+     *
+     * # Returns
+     * The breach once a limit has been exceeded for `duration_secs`, then `None` until usage
+     * goes back under the limits.
+     */
+    pub fn update(
+        &mut self,
+        limits: &ResourceLimits,
+        sample: Sample,
+        now: Instant,
+    ) -> Option<Breach> {
+        let Some(resource) = limits.exceeded_by(&sample) else {
+            *self = Self::default();
+            return None;
+        };
+
+        let since = match self.exceeded {
+            Some((tracked, since)) if tracked == resource => since,
+            _ => {
+                self.exceeded = Some((resource, now));
+                self.reported = false;
+                now
+            }
+        };
+
+        let grace = Duration::from_secs(limits.duration_secs);
+        if self.reported || now.saturating_duration_since(since) < grace {
+            return None;
+        }
+        self.reported = true;
+        Some(Breach {
+            resource,
+            sample,
+            limits: limits.clone(),
+        })
+    }
+}
+
+/// Recent restarts of one executable done by the runner
+#[derive(Debug, Clone, Default)]
+pub struct RestartBudget {
+    restarts: VecDeque<Instant>,
+}
+
+impl RestartBudget {
+    /**
+     * Records a restart at `now` if fewer than [MAX_RESTARTS] happened in the last
+     * [RESTART_WINDOW].
+     *
+     * # Returns
+     * Whether the restart is allowed.
+     */
+    pub fn try_restart(&mut self, now: Instant) -> bool {
+        while self
+            .restarts
+            .front()
+            .is_some_and(|&at| now.saturating_duration_since(at) >= RESTART_WINDOW)
+        {
+            self.restarts.pop_front();
+        }
+        if self.restarts.len() >= MAX_RESTARTS {
+            return false;
+        }
+        self.restarts.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn sample(cpu_percent: f32, working_set: u64) -> Sample {
+        Sample {
+            cpu_percent,
+            working_set,
+        }
+    }
+
+    fn memory_limits(max_memory_mb: u64, duration_secs: u64) -> ResourceLimits {
+        ResourceLimits {
+            max_memory_mb: Some(max_memory_mb),
+            duration_secs,
+            action: LimitAction::Restart,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn breach_after_the_whole_duration() {
+        let start = Instant::now();
+        let limits = memory_limits(1536, 60);
+        let mut tracker = LimitTracker::default();
+
+        for second in 0..60 {
+            let now = start + Duration::from_secs(second);
+            assert_eq!(tracker.update(&limits, sample(1.0, 1600 * MB), now), None);
+        }
+        let breach = tracker
+            .update(
+                &limits,
+                sample(1.0, 1600 * MB),
+                start + Duration::from_secs(60),
+            )
+            .unwrap();
+        assert_eq!(breach.resource, Resource::Memory);
+        assert_eq!(
+            breach.describe(),
+            "working set 1.6 GB above the 1.5 GB limit for 60s"
+        );
+
+        // Reported once per episode
+        let later = start + Duration::from_secs(90);
+        assert_eq!(tracker.update(&limits, sample(1.0, 1600 * MB), later), None);
+    }
+
+    #[test]
+    fn dropping_under_the_limit_resets_the_duration() {
+        let start = Instant::now();
+        let limits = memory_limits(100, 10);
+        let mut tracker = LimitTracker::default();
+
+        tracker.update(&limits, sample(0.0, 200 * MB), start);
+        tracker.update(
+            &limits,
+            sample(0.0, 50 * MB),
+            start + Duration::from_secs(5),
+        );
+        let at = |second| start + Duration::from_secs(second);
+        assert_eq!(tracker.update(&limits, sample(0.0, 200 * MB), at(6)), None);
+        assert_eq!(tracker.update(&limits, sample(0.0, 200 * MB), at(15)), None);
+        assert!(
+            tracker
+                .update(&limits, sample(0.0, 200 * MB), at(16))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn cpu_limit() {
+        let start = Instant::now();
+        let limits = ResourceLimits {
+            max_cpu_percent: Some(25.0),
+            duration_secs: 0,
+            ..Default::default()
+        };
+        let mut tracker = LimitTracker::default();
+
+        assert_eq!(tracker.update(&limits, sample(20.0, 0), start), None);
+        let breach = tracker.update(&limits, sample(30.0, 0), start).unwrap();
+        assert_eq!(breach.resource, Resource::Cpu);
+        assert_eq!(breach.describe(), "CPU 30.0% above the 25.0% limit for 0s");
+    }
+
+    #[test]
+    fn validation_and_defaults() {
+        assert!(ResourceLimits::default().is_empty());
+        assert!(memory_limits(0, 60).validate().is_err());
+        assert!(memory_limits(1536, 1).validate().is_err());
+        assert!(memory_limits(1536, MIN_DURATION_SECS).validate().is_ok());
+        assert!(
+            ResourceLimits {
+                max_cpu_percent: Some(150.0),
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+
+        let limits: ResourceLimits = serde_json::from_str(r#"{"max_memory_mb":1536}"#).unwrap();
+        assert_eq!(limits.duration_secs, 60);
+        assert_eq!(limits.action, LimitAction::Warn);
+    }
+
+    #[test]
+    fn restarts_are_capped_within_the_window() {
+        let start = Instant::now();
+        let mut budget = RestartBudget::default();
+        let at = |second| start + Duration::from_secs(second);
+
+        for second in 0..MAX_RESTARTS as u64 {
+            assert!(budget.try_restart(at(second * 60)));
+        }
+        assert!(!budget.try_restart(at(300)));
+        // The first restart left the window
+        assert!(budget.try_restart(at(RESTART_WINDOW.as_secs())));
+        assert!(!budget.try_restart(at(RESTART_WINDOW.as_secs() + 1)));
+    }
+}
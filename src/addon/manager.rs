@@ -3,6 +3,11 @@
 
 Handles all executable management functionality ,including:
 - Persistent storage of executable paths
+- Grouping of executables
+- Profiles selecting which executables are enabled and launched on startup
+- Applying map based launch triggers
+- Applying character select / in-game transition triggers
+- Suspending and resuming processes, and applying focus policies
 - Launching and stopping processes
 - Process tracking and cleanup
 - File dialog integration for selecting executables
@@ -10,35 +15,286 @@ Handles all executable management functionality ,including:
 */
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fs::{read_to_string, write},
     path::PathBuf,
-    process::{Child, Command, Stdio},
+    process::{Command, Stdio},
     sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Instant, SystemTime},
 };
 
 use serde::{Deserialize, Serialize};
 
-use crate::addon::{NexusError, Result};
+use crate::addon::{
+    NexusError, Result,
+    event_payload::StatusCode,
+    focus::FocusPolicy,
+    game_state::GameEvent,
+    health::{HealthCheck, HealthStatus},
+    keybinds, launch,
+    limits::{Breach, LimitAction, ResourceLimits, RestartBudget},
+    process::{self, Priority, RunningProcess},
+    readiness::{Readiness, ReadinessTracker, ReadyState},
+    schedule::Schedule,
+    triggers::{self, MapSnapshot, MapTriggers, TriggerAction},
+    wine, wine_path,
+};
 
 /// Stores a list of executable paths, tracks running processes, and provides methods for launching, stopping,
 /// and cleaning up executables. All operations return a `Result<T, NexusError>`.
 /// Executable list is persisted in JSON format in the addon directory.
 #[derive(Debug)]
 pub struct ExeManager {
-    running_processes: HashMap<String, Child>,
+    running_processes: HashMap<String, RunningProcess>,
+    /// Launch time of the running processes
+    launched_at: HashMap<String, SystemTime>,
     addon_dir: PathBuf,
     executables: Vec<Executable>,
+    groups: Vec<Group>,
+    profiles: Vec<Profile>,
+    active_profile: Option<String>,
+    /// Id given to the next added executable, ids of removed executables are never reused
+    next_id: u32,
+    /// Paths of the running executables whose process is suspended
+    suspended: HashSet<String>,
+    /// Paths of the executables stopped by a focus loss, to launch again on focus gain
+    stopped_on_focus_loss: HashSet<String>,
+    /// Process lifecycle changes not yet reported through the event API
+    state_changes: Vec<StateChange>,
+    /// Incremented on every process lifecycle change
+    revision: u64,
+    /// Launches, exits and interventions of the runner, oldest first
+    run_history: VecDeque<RunRecord>,
+    /// Result of the latest health probes of the running executables
+    health: HashMap<String, HealthStatus>,
+    /// Startup of the running executables that have a readiness probe
+    readiness: HashMap<String, ReadinessTracker>,
+    /// Recent restarts done by the runner, by path
+    restarts: HashMap<String, RestartBudget>,
+    /// Paths of the elevated executables whose UAC prompt is shown
+    prompting: HashSet<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Executable {
+    /// Stable identifier, unique within the list and kept across renames
+    #[serde(default)]
+    pub id: u32,
     pub path: String,
+    /// How the path is launched
+    #[serde(default, skip_serializing_if = "ExeKind::is_windows")]
+    pub kind: ExeKind,
+    /// Display name, the path is shown when empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// Command line arguments, passed verbatim to the process
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub args: String,
+    /// Working directory, the addon process' one is used when empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub working_dir: String,
+    /// Extra environment variables set for the process
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
     pub launch_on_startup: bool,
+    /// Map and game mode based launch triggers
+    #[serde(default, skip_serializing_if = "MapTriggers::is_empty")]
+    pub map_triggers: MapTriggers,
+    /// Game transition that launches the executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_on: Option<GameEvent>,
+    /// Game transition that stops the executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_on: Option<GameEvent>,
+    /// Interval or time of day the executable is launched at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    /// What happens when the game window loses or regains focus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_policy: Option<FocusPolicy>,
+    /// Default key combination of the toggle keybind, e.g. `ALT+SHIFT+B`.
+    /// Only used the first time the keybind is registered, it can then be
+    /// changed in the Nexus keybind settings.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub keybind: String,
+    /// Launched with administrator rights through the UAC prompt
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub elevated: bool,
+    /// Scheduling priority set after the process is spawned
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
+    /// Cores the process may run on, one bit per core, `0` for all of them
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub affinity: u64,
+    /// Usage limits enforced by the monitor while the executable runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ResourceLimits>,
+    /// What makes the executable ready after its launch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readiness: Option<Readiness>,
+    /// Probe run periodically while the executable runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    /// Name of the group this executable belongs to, `None` when ungrouped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     #[serde(skip)]
     pub is_running: bool,
 }
 
+impl Executable {
+    /// Returns the name to show in the UI, falling back to the path
+    pub fn display_name(&self) -> &str {
+        if self.name.trim().is_empty() {
+            &self.path
+        } else {
+            &self.name
+        }
+    }
+}
+
+/// Kind of program an executable entry launches
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExeKind {
+    /// Windows program, launched directly
+    #[default]
+    Windows,
+    /// Native Linux program, launched through wine's `start.exe /unix`.
+    /// Only the bridge is tracked, the program can't be stopped, suspended or measured.
+    LinuxNative,
+    /// URL or protocol link opened with its default handler, never tracked as running
+    Url,
+}
+
+impl ExeKind {
+    pub const ALL: [ExeKind; 3] = [ExeKind::Windows, ExeKind::LinuxNative, ExeKind::Url];
+
+    pub fn is_windows(&self) -> bool {
+        *self == ExeKind::Windows
+    }
+
+    /// Checks whether the tracked process is the program itself, so it can be controlled
+    pub fn tracks_program(&self) -> bool {
+        *self == ExeKind::Windows
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExeKind::Windows => "Windows program",
+            ExeKind::LinuxNative => "Native Linux program",
+            ExeKind::Url => "Link",
+        }
+    }
+}
+
+/// Lifecycle state of an executable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExeState {
+    Stopped,
+    Running,
+    Suspended,
+}
+
+impl From<ExeState> for StatusCode {
+    fn from(state: ExeState) -> Self {
+        match state {
+            ExeState::Stopped => StatusCode::Stopped,
+            ExeState::Running => StatusCode::Running,
+            ExeState::Suspended => StatusCode::Suspended,
+        }
+    }
+}
+
+/// Process lifecycle change, reported to other addons through the event API
+#[derive(Debug, Clone)]
+pub struct StateChange {
+    pub id: u32,
+    pub path: String,
+    pub name: String,
+    pub state: StatusCode,
+    pub exit_code: Option<i32>,
+}
+
+/// Maximum number of entries kept in the run history
+const RUN_HISTORY_CAPACITY: usize = 200;
+
+/// Entry of the run history, shown in the History tab
+#[derive(Debug, Clone)]
+pub struct RunRecord {
+    pub at: SystemTime,
+    /// Display name of the executable at the time of the entry
+    pub name: String,
+    pub message: String,
+}
+
+/// A named group used to organize executables in the list
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Group {
+    pub name: String,
+    /// Launch every executable of the group on addon load
+    #[serde(default)]
+    pub launch_on_startup: bool,
+}
+
+/// A named set of enabled executables with its own startup selection
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Profile {
+    pub name: String,
+    /// Executables enabled in this profile
+    #[serde(default)]
+    pub entries: Vec<ProfileEntry>,
+    /// Character names that automatically select this profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub characters: Vec<String>,
+    /// Profession ids (as reported by MumbleLink) that automatically select this profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub professions: Vec<u32>,
+    /// Account names that automatically select this profile
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub accounts: Vec<String>,
+}
+
+/// An executable enabled in a profile, referenced by path
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProfileEntry {
+    pub path: String,
+    /// Launch the executable on addon load or when switching to the profile
+    #[serde(default)]
+    pub launch_on_startup: bool,
+}
+
+impl Profile {
+    fn entry(&self, path: &str) -> Option<&ProfileEntry> {
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+}
+
+/// On-disk layout of exes.json
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct ExeConfig {
+    #[serde(default)]
+    groups: Vec<Group>,
+    #[serde(default)]
+    executables: Vec<Executable>,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    active_profile: Option<String>,
+    /// Id given to the next added executable, `0` in files saved before it existed
+    #[serde(default)]
+    next_id: u32,
+}
+
+/// Accepts both the current config object and the legacy plain executable array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredExeConfig {
+    Config(ExeConfig),
+    Legacy(Vec<Executable>),
+}
+
 impl ExeManager {
     /// Creates a new ExeManager instance and loads the existing exe list from disk.
     ///
@@ -50,8 +306,22 @@ impl ExeManager {
     pub fn new(addon_dir: PathBuf) -> Result<Self> {
         let mut manager = Self {
             running_processes: HashMap::new(),
+            launched_at: HashMap::new(),
             addon_dir,
             executables: Vec::new(),
+            groups: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: None,
+            next_id: 1,
+            suspended: HashSet::new(),
+            stopped_on_focus_loss: HashSet::new(),
+            state_changes: Vec::new(),
+            revision: 0,
+            run_history: VecDeque::new(),
+            health: HashMap::new(),
+            readiness: HashMap::new(),
+            restarts: HashMap::new(),
+            prompting: HashSet::new(),
         };
         manager.load_exe_list()?;
         Ok(manager)
@@ -61,6 +331,20 @@ impl ExeManager {
         &self.executables
     }
 
+    pub fn groups(&self) -> &Vec<Group> {
+        &self.groups
+    }
+
+    pub fn profiles(&self) -> &Vec<Profile> {
+        &self.profiles
+    }
+
+    /// Returns the active profile, `None` when every executable is enabled
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let name = self.active_profile.as_deref()?;
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
     /// Loads the executable list from the exes.json file in the addon directory.
     ///
     /// # Errors
@@ -71,12 +355,29 @@ impl ExeManager {
 
         match read_to_string(&exes_file) {
             Ok(contents) => match serde_json::from_str(&contents) {
-                Ok(executables) => {
-                    self.executables = executables;
+                Ok(stored) => {
+                    let config = match stored {
+                        StoredExeConfig::Config(config) => config,
+                        StoredExeConfig::Legacy(executables) => ExeConfig {
+                            executables,
+                            ..Default::default()
+                        },
+                    };
+                    self.executables = config.executables;
+                    self.groups = config.groups;
+                    self.profiles = config.profiles;
+                    self.active_profile = config
+                        .active_profile
+                        .filter(|name| self.profiles.iter().any(|p| &p.name == name));
+                    self.next_id = config.next_id.max(1);
                     log::info!(
-                        "Loaded {} executables from exe list",
-                        self.executables.len()
+                        "Loaded {} executables and {} groups from exe list",
+                        self.executables.len(),
+                        self.groups.len()
                     );
+                    if self.assign_missing_ids() {
+                        self.save_exe_list()?;
+                    }
                     Ok(())
                 }
                 Err(e) => {
@@ -105,7 +406,15 @@ impl ExeManager {
         let mut exes_file = self.addon_dir.clone();
         exes_file.push("exes.json");
 
-        match serde_json::to_string_pretty(&self.executables) {
+        let config = ExeConfig {
+            groups: self.groups.clone(),
+            executables: self.executables.clone(),
+            profiles: self.profiles.clone(),
+            active_profile: self.active_profile.clone(),
+            next_id: self.next_id,
+        };
+
+        match serde_json::to_string_pretty(&config) {
             Ok(content) => {
                 write(&exes_file, content).map_err(|e| {
                     let error_msg = format!("Failed to save exe list to {:?}: {}", exes_file, e);
@@ -123,48 +432,235 @@ impl ExeManager {
         }
     }
 
+    /// Gives an id to the executables saved before ids existed, fixes duplicated ids and
+    /// moves the next id past every id in use. Returns whether anything changed.
+    fn assign_missing_ids(&mut self) -> bool {
+        let mut seen = HashSet::new();
+        let max_id = self.executables.iter().map(|exe| exe.id).max().unwrap_or(0);
+        let mut changed = self.next_id <= max_id;
+        self.next_id = self.next_id.max(max_id + 1);
+
+        for index in 0..self.executables.len() {
+            let id = self.executables[index].id;
+            if id == 0 || !seen.insert(id) {
+                let id = self.allocate_id();
+                self.executables[index].id = id;
+                seen.insert(id);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Returns a new executable id, never given before
+    fn allocate_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Returns the executable with the given id
+    pub fn executable_by_id(&self, id: u32) -> Option<&Executable> {
+        self.executables.iter().find(|exe| exe.id == id)
+    }
+
     /**
      * Adds a new executable path to the list and persists it.
      *
      * # Arguments
      * * `path` - Path to the executable file
      *
+     * # Returns
+     * The id of the new executable, or of the existing one if the path is already in the list.
+     *
      * # Errors
      * Returns `NexusError::FileOperation` if the path is empty or saving fails.
      */
-    pub fn add_exe(&mut self, path: String) -> Result<()> {
-        if path.trim().is_empty() {
-            return Err(NexusError::FileOperation(
-                "Cannot add empty executable path".to_string(),
-            ));
-        }
+    pub fn add_exe(&mut self, path: String) -> Result<u32> {
+        self.add_exe_of_kind(path, ExeKind::Windows)
+    }
 
-        // Basic validation: path exists and is a file
-        let pb = PathBuf::from(&path);
-        if !pb.exists() || !pb.is_file() {
-            return Err(NexusError::FileOperation(format!(
-                "Executable path does not exist or is not a file: {}",
-                path
-            )));
-        }
+    /**
+     * Adds a new program of the given kind to the list and persists it.
+     *
+     * # Arguments
+     * * `path` - Path of the program, a Unix path for native Linux programs
+     * * `kind` - How the program is launched
+     *
+     * # Returns
+     * The id of the new executable, or of the existing one if the path is already in the list.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the path is invalid for its kind or saving fails.
+     */
+    pub fn add_exe_of_kind(&mut self, path: String, kind: ExeKind) -> Result<u32> {
+        let path = normalize_exe_path(&path, kind);
+        validate_exe_path(&path, kind)?;
 
-        if self.executables.iter().any(|exe| exe.path == path) {
+        if let Some(exe) = self.executables.iter().find(|exe| exe.path == path) {
             log::warn!("Executable path already exists: {path}");
-            return Ok(());
+            return Ok(exe.id);
         }
 
+        let id = self.allocate_id();
         self.executables.push(Executable {
+            id,
             path: path.clone(),
-            launch_on_startup: false,
-            is_running: false,
+            kind,
+            ..Default::default()
         });
         self.save_exe_list()?;
         log::info!("Added executable: {path}");
-        Ok(())
+        Ok(id)
+    }
+
+    /**
+     * Replaces every persisted field of an executable at once.
+     * The update is validated like `add_exe` and nothing is changed if validation fails.
+     * The startup flag is stored like [ExeManager::set_launch_on_startup] does.
+     * If the executable is running, its process keeps running and is tracked under the
+     * new path; the new launch settings apply on the next launch.
+     *
+     * # Arguments
+     * * `index` - Index of the executable in the list
+     * * `updated` - New values for the executable
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the index is invalid, validation fails or saving fails.
+     */
+    pub fn update_exe(&mut self, index: usize, mut updated: Executable) -> Result<()> {
+        if index >= self.executables.len() {
+            return Err(NexusError::FileOperation(format!(
+                "Invalid index {} for exe list of length {}",
+                index,
+                self.executables.len()
+            )));
+        }
+
+        updated.path = normalize_exe_path(&updated.path, updated.kind);
+        if !updated.working_dir.trim().is_empty() {
+            updated.working_dir = normalize_exe_path(&updated.working_dir, ExeKind::Windows);
+        }
+        validate_exe_path(&updated.path, updated.kind)?;
+        if let Some(limits) = &updated.limits {
+            limits.validate().map_err(NexusError::FileOperation)?;
+        }
+        // Empty limits are not persisted
+        updated.limits = updated.limits.filter(|limits| !limits.is_empty());
+        if let Some(health_check) = &updated.health_check {
+            health_check.validate().map_err(NexusError::FileOperation)?;
+        }
+        if let Some(readiness) = &updated.readiness {
+            readiness.validate().map_err(NexusError::FileOperation)?;
+        }
+        process::validate_affinity(updated.affinity, process::core_count())
+            .map_err(NexusError::FileOperation)?;
+        if updated.kind == ExeKind::LinuxNative {
+            validate_native(&updated)?;
+        }
+        if updated.elevated {
+            validate_elevated(&updated)?;
+        }
+
+        if self
+            .executables
+            .iter()
+            .enumerate()
+            .any(|(i, exe)| i != index && exe.path == updated.path)
+        {
+            return Err(NexusError::FileOperation(format!(
+                "Executable path already exists: {}",
+                updated.path
+            )));
+        }
+
+        if !updated.working_dir.trim().is_empty() && !PathBuf::from(&updated.working_dir).is_dir() {
+            return Err(NexusError::FileOperation(format!(
+                "Working directory does not exist: {}",
+                updated.working_dir
+            )));
+        }
+
+        if let Some(key) = updated
+            .env
+            .keys()
+            .find(|key| key.trim().is_empty() || key.contains('='))
+        {
+            return Err(NexusError::FileOperation(format!(
+                "Invalid environment variable name: '{key}'"
+            )));
+        }
+
+        if let Some(schedule) = &updated.schedule {
+            schedule.validate().map_err(NexusError::FileOperation)?;
+        }
+
+        if let Some(name) = &updated.group {
+            if !self.groups.iter().any(|group| &group.name == name) {
+                return Err(NexusError::FileOperation(format!(
+                    "Group does not exist: {name}"
+                )));
+            }
+        }
+
+        let launch_on_startup = updated.launch_on_startup;
+        self.check_launch_on_startup(index, launch_on_startup)?;
+
+        let previous = &self.executables[index];
+        updated.id = previous.id;
+        updated.is_running = previous.is_running;
+        updated.launch_on_startup = previous.launch_on_startup;
+        if previous.path != updated.path {
+            for profile in &mut self.profiles {
+                for entry in &mut profile.entries {
+                    if entry.path == previous.path {
+                        entry.path = updated.path.clone();
+                    }
+                }
+            }
+
+            if self.running_processes.contains_key(&previous.path) {
+                log::info!(
+                    "Executable {} is running, tracking it under its new path {}",
+                    previous.path,
+                    updated.path
+                );
+            }
+            let previous_path = previous.path.clone();
+            self.move_tracked_state(&previous_path, &updated.path);
+        }
+
+        log::info!("Updated executable: {}", updated.path);
+        self.executables[index] = updated;
+        self.store_launch_on_startup(index, launch_on_startup);
+        self.save_exe_list()
+    }
+
+    /// Moves everything tracked about an executable from its previous path to its new one
+    fn move_tracked_state(&mut self, from: &str, to: &str) {
+        fn move_value<V>(map: &mut HashMap<String, V>, from: &str, to: &str) {
+            if let Some(value) = map.remove(from) {
+                map.insert(to.to_string(), value);
+            }
+        }
+        fn move_path(set: &mut HashSet<String>, from: &str, to: &str) {
+            if set.remove(from) {
+                set.insert(to.to_string());
+            }
+        }
+        move_value(&mut self.running_processes, from, to);
+        move_value(&mut self.launched_at, from, to);
+        move_value(&mut self.health, from, to);
+        move_value(&mut self.readiness, from, to);
+        move_value(&mut self.restarts, from, to);
+        move_path(&mut self.suspended, from, to);
+        move_path(&mut self.stopped_on_focus_loss, from, to);
+        move_path(&mut self.prompting, from, to);
     }
 
     /**
-     * Removes an executable from the list by index and stops its process if running.
+     * Removes an executable from the list by index, stops its process if running and
+     * unregisters its toggle keybind.
      *
      * # Arguments
      * * `index` - Index of the executable in the list
@@ -181,64 +677,254 @@ impl ExeManager {
             )));
         }
 
-        let path = self.executables.remove(index).path;
+        let removed = self.executables.remove(index);
+        let path = removed.path.clone();
+
+        for profile in &mut self.profiles {
+            profile.entries.retain(|entry| entry.path != path);
+        }
+
+        self.suspended.remove(&path);
+        self.stopped_on_focus_loss.remove(&path);
+        self.launched_at.remove(&path);
+        // A UAC prompt still shown launches nothing once accepted
+        self.prompting.remove(&path);
 
-        // Kill the process if it's running
-        if let Some(mut child) = self.running_processes.remove(&path) {
+        // Kill the process if it's running, native Linux programs are only forgotten
+        if let Some(mut child) = self
+            .running_processes
+            .remove(&path)
+            .filter(|_| removed.kind.tracks_program())
+        {
             if let Err(e) = child.kill() {
                 log::warn!("Failed to kill process for removed executable {path}: {e}");
             } else {
                 log::info!("Stopped process for removed executable: {path}");
+                self.state_changes.push(StateChange {
+                    id: removed.id,
+                    name: removed.display_name().to_string(),
+                    path: path.clone(),
+                    state: StatusCode::Stopped,
+                    exit_code: None,
+                });
             }
         }
 
         self.save_exe_list()?;
+        keybinds::unregister_exe_keybind(removed.id);
         log::info!("Removed executable: {path}");
         Ok(())
     }
 
     /**
-     * Launches an executable by path.
+     * Moves an executable to a new position in the list and persists the order.
+     * The list order drives the display order and the startup launch order.
+     *
+     * # Arguments
+     * * `from` - Current index of the executable
+     * * `to` - Index the executable should end up at
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if an index is invalid or saving fails.
+     */
+    pub fn move_exe(&mut self, from: usize, to: usize) -> Result<()> {
+        let len = self.executables.len();
+        if from >= len || to >= len {
+            return Err(NexusError::FileOperation(format!(
+                "Invalid move from {from} to {to} for exe list of length {len}"
+            )));
+        }
+        if from == to {
+            return Ok(());
+        }
+
+        let exe = self.executables.remove(from);
+        log::info!("Moved executable {} from {from} to {to}", exe.path);
+        self.executables.insert(to, exe);
+        self.save_exe_list()
+    }
+
+    /**
+     * Launches an executable by path. Elevated executables are launched once their UAC
+     * prompt is accepted, see [ExeManager::is_prompting].
      *
      * # Arguments
      * * `path` - Path to the executable file
      *
      * # Errors
-     * Returns `NexusError::ProcessLaunch` if the process is already running or spawning fails.
+     * Returns `NexusError::ProcessLaunch` if the process is already running or its UAC prompt
+     * is shown, a shortcut can't be resolved or spawning fails.
      */
     pub fn launch_exe(&mut self, path: &str) -> Result<()> {
-        use std::os::windows::process::CommandExt;
-
         if self.running_processes.contains_key(path) {
             return Err(NexusError::ProcessLaunch(format!(
                 "Process is already running: {path}"
             )));
         }
+        if self.prompting.contains(path) {
+            return Err(NexusError::ProcessLaunch(format!(
+                "The UAC prompt of {path} is already shown"
+            )));
+        }
 
-        // Update the is_running flag in the executables vector
-        if let Some(executable) = self.executables.iter_mut().find(|exe| exe.path == path) {
-            executable.is_running = true;
+        let executable = self
+            .executables
+            .iter()
+            .find(|exe| exe.path == path)
+            .cloned()
+            .unwrap_or_else(|| Executable {
+                path: path.to_string(),
+                ..Default::default()
+            });
+
+        // Links are handed to their default handler and not tracked
+        if executable.kind == ExeKind::Url {
+            return process::shell_open(path)
+                .map(|()| log::info!("Opened link: {path}"))
+                .map_err(|e| {
+                    let error_msg = format!("Failed to open {path}: {e}");
+                    log::error!("{error_msg}");
+                    NexusError::ProcessLaunch(error_msg)
+                });
         }
 
-        // CREATE_NO_WINDOW | DETACHED_PROCESS to avoid inheriting console
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        const DETACHED_PROCESS: u32 = 0x00000008;
-        match Command::new(path)
-            .creation_flags(CREATE_NO_WINDOW | DETACHED_PROCESS)
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn()
-        {
-            Ok(child) => {
-                log::info!("Launched executable: {path}");
-                self.running_processes.insert(path.to_string(), child);
+        // Elevated executables go through the UAC prompt, which only takes a command line
+        if executable.elevated {
+            let plan = elevated_plan(path, &executable)?;
+            return self.launch_elevated(executable, plan);
+        }
+        let mut command = build_command(path, &executable)?;
+
+        // Read before the spawn so log lines written right away are not missed
+        let readiness = executable
+            .readiness
+            .as_ref()
+            .map(|readiness| ReadinessTracker::new(readiness, Instant::now()));
+
+        // Windows programs run in a job holding the processes they start, and get their
+        // priority and affinity before they run
+        let spawned = if executable.kind.tracks_program() {
+            process::spawn_in_job(&mut command, |process| {
+                apply_scheduling(process, &executable)
+            })
+        } else {
+            command.spawn().map(RunningProcess::from)
+        };
+        match spawned {
+            Ok(process) => {
+                self.track_launch(path, process, readiness);
                 Ok(())
             }
             Err(e) => {
-                // Reset the is_running flag on failure
-                if let Some(executable) = self.executables.iter_mut().find(|exe| exe.path == path) {
-                    executable.is_running = false;
+                let error_msg = format!("Failed to launch {path}: {e}");
+                log::error!("{error_msg}");
+                Err(NexusError::ProcessLaunch(error_msg))
+            }
+        }
+    }
+
+    /**
+     * Shows the UAC prompt of an elevated executable on a worker thread, the game keeps
+     * running while it is answered. The process is tracked once the prompt returns, see
+     * [ExeManager::finish_elevated_launch].
+     *
+     * # Errors
+     * Returns `NexusError::ProcessLaunch` if the worker thread can't be started.
+     */
+    fn launch_elevated(&mut self, executable: Executable, plan: launch::LaunchPlan) -> Result<()> {
+        let path = executable.path.clone();
+        self.prompting.insert(path.clone());
+        let prompt = thread::Builder::new()
+            .name("exe-runner-elevation".to_string())
+            .spawn(move || {
+                let result = process::run_elevated(
+                    &plan.program,
+                    &plan.command_line(),
+                    plan.working_dir.as_deref(),
+                );
+                let Some(exe_manager) = EXE_MANAGER.get() else {
+                    return;
+                };
+                match exe_manager.lock() {
+                    Ok(mut exe_manager) => {
+                        match exe_manager.finish_elevated_launch(&executable, result) {
+                            // Declining the UAC prompt is the user's choice, not a failure
+                            Ok(()) | Err(NexusError::ElevationCancelled(_)) => {}
+                            Err(e) => log::error!("Failed to launch executable: {e}"),
+                        }
+                    }
+                    Err(e) => log::error!("Failed to lock exe manager after the UAC prompt: {e}"),
                 }
+            })
+            .map_err(|e| {
+                self.prompting.remove(&path);
+                let error_msg = format!("Failed to show the UAC prompt of {path}: {e}");
+                log::error!("{error_msg}");
+                NexusError::ProcessLaunch(error_msg)
+            })?;
+
+        log::info!("Waiting for the UAC prompt of {path}");
+        if let Ok(mut prompts) = ELEVATION_PROMPTS.lock() {
+            prompts.retain(|prompt| !prompt.is_finished());
+            prompts.push(prompt);
+        }
+        Ok(())
+    }
+
+    /**
+     * Tracks the process of an elevated executable once its UAC prompt returned. A process
+     * whose launch was abandoned while the prompt was shown, by stopping everything or
+     * removing the executable, is stopped right away. A declined prompt is raised as a
+     * `StatusCode::LaunchCancelled` state change.
+     *
+     * # Arguments
+     * * `executable` - Executable as it was when launched, found again by id if renamed since
+     * * `result` - Elevated process, or why it was not started
+     *
+     * # Errors
+     * Returns `NexusError::ElevationCancelled` if the UAC prompt was declined, and
+     * `NexusError::ProcessLaunch` if the program could not be started.
+     */
+    fn finish_elevated_launch(
+        &mut self,
+        executable: &Executable,
+        result: std::io::Result<RunningProcess>,
+    ) -> Result<()> {
+        // The executable may have been renamed while the prompt was shown
+        let path = self
+            .executables
+            .iter()
+            .find(|exe| exe.id == executable.id)
+            .map_or_else(|| executable.path.clone(), |exe| exe.path.clone());
+        let path = path.as_str();
+        let expected = self.prompting.remove(path);
+        match result {
+            Ok(mut process) if !expected => {
+                log::info!("Launch of {path} was abandoned during its UAC prompt, stopping it");
+                process.kill().map_err(|e| {
+                    let error_msg = format!("Failed to stop {path}: {e}");
+                    log::error!("{error_msg}");
+                    NexusError::ProcessStop(error_msg)
+                })
+            }
+            Ok(process) => {
+                // The UAC prompt starts the process, it can only be changed once running
+                apply_scheduling(&process, executable);
+                // Answering the prompt doesn't count against the readiness timeout
+                let readiness = executable
+                    .readiness
+                    .as_ref()
+                    .map(|readiness| ReadinessTracker::new(readiness, Instant::now()));
+                self.track_launch(path, process, readiness);
+                Ok(())
+            }
+            Err(e) if process::is_elevation_cancelled(&e) => {
+                log::info!("Elevation prompt declined for {path}");
+                self.record_state_change(path, StatusCode::LaunchCancelled, None);
+                self.add_run_record(path, "Elevation prompt declined".to_string());
+                Err(NexusError::ElevationCancelled(path.to_string()))
+            }
+            Err(e) => {
                 let error_msg = format!("Failed to launch {path}: {e}");
                 log::error!("{error_msg}");
                 Err(NexusError::ProcessLaunch(error_msg))
@@ -246,6 +932,50 @@ impl ExeManager {
         }
     }
 
+    /// Starts tracking the freshly launched process of an executable
+    fn track_launch(
+        &mut self,
+        path: &str,
+        process: RunningProcess,
+        readiness: Option<ReadinessTracker>,
+    ) {
+        log::info!("Launched executable: {path}");
+        // Update the is_running flag in the executables vector
+        if let Some(executable) = self.executables.iter_mut().find(|exe| exe.path == path) {
+            executable.is_running = true;
+        }
+        self.running_processes.insert(path.to_string(), process);
+        self.launched_at.insert(path.to_string(), SystemTime::now());
+        self.record_state_change(path, StatusCode::Running, None);
+        self.add_run_record(path, "Launched".to_string());
+        self.health.remove(path);
+        match readiness {
+            Some(readiness) => self.readiness.insert(path.to_string(), readiness),
+            None => self.readiness.remove(path),
+        };
+    }
+
+    /// Checks if the UAC prompt of an elevated executable is waiting for an answer
+    pub fn is_prompting(&self, path: &str) -> bool {
+        self.prompting.contains(path)
+    }
+
+    /**
+     * Launches an executable for a trigger, a schedule or another addon rather than the user.
+     * Elevated executables are refused, their UAC prompt would show up unasked.
+     *
+     * # Errors
+     * Returns `NexusError::ProcessLaunch` if the executable is elevated or launching fails.
+     */
+    pub fn launch_exe_unattended(&mut self, path: &str) -> Result<()> {
+        if self.is_elevated(path) {
+            return Err(NexusError::ProcessLaunch(format!(
+                "Elevated executables are only launched on request: {path}"
+            )));
+        }
+        self.launch_exe(path)
+    }
+
     /**
      * Stops a running executable by path.
      *
@@ -253,13 +983,22 @@ impl ExeManager {
      * * `path` - Path to the executable file
      *
      * # Errors
-     * Returns `NexusError::ProcessStop` if the process is not running or killing fails.
+     * Returns `NexusError::ProcessStop` if the process is not running, is a native Linux
+     * program or killing fails.
      */
     pub fn stop_exe(&mut self, path: &str) -> Result<()> {
+        if self.is_untracked_program(path) {
+            return Err(NexusError::ProcessStop(format!(
+                "Native Linux programs can't be stopped from the runner: {path}"
+            )));
+        }
+
         // Reset the is_running flag in the executables vector
         if let Some(executable) = self.executables.iter_mut().find(|exe| exe.path == path) {
             executable.is_running = false;
         }
+        self.suspended.remove(path);
+        self.launched_at.remove(path);
 
         if let Some(mut child) = self.running_processes.remove(path) {
             match child.kill() {
@@ -267,6 +1006,8 @@ impl ExeManager {
                     log::info!("Stopped executable: {path}");
                     // Best-effort wait to reap handle
                     let _ = child.wait();
+                    self.record_state_change(path, StatusCode::Stopped, None);
+                    self.add_run_record(path, "Stopped".to_string());
                     Ok(())
                 }
                 Err(e) => {
@@ -283,35 +1024,235 @@ impl ExeManager {
     }
 
     /**
-     * Cleans up finished processes from the running processes map.
-     * Should be called periodically to avoid resource leaks.
+     * Launches an executable if it is stopped, stops it otherwise.
+     *
+     * # Arguments
+     * * `id` - Id of the executable
+     *
+     * # Errors
+     * Returns `NexusError::ProcessLaunch` if there is no executable with this id or launching
+     * fails, `NexusError::ProcessStop` if stopping fails.
      */
-    pub fn cleanup_finished_processes(&mut self) {
-        let mut finished = Vec::new();
-
-        for (path, child) in &mut self.running_processes {
-            if let Ok(Some(_)) = child.try_wait() {
-                finished.push(path.clone());
-            }
-        }
+    pub fn toggle_exe(&mut self, id: u32) -> Result<()> {
+        let Some(path) = self.executable_by_id(id).map(|exe| exe.path.clone()) else {
+            return Err(NexusError::ProcessLaunch(format!(
+                "No executable with id {id}"
+            )));
+        };
 
-        for path in finished {
-            self.running_processes.remove(&path);
-            // Reset the is_running flag in the executables vector
-            if let Some(executable) = self.executables.iter_mut().find(|exe| exe.path == path) {
-                executable.is_running = false;
-            }
-            log::info!("Process finished: {path}");
+        self.cleanup_finished_processes();
+        if self.running_processes.contains_key(&path) {
+            self.stop_exe(&path)
+        } else {
+            self.launch_exe(&path)
         }
     }
 
     /**
-     * Checks if an executable is currently running.
+     * Suspends a running executable without terminating it.
      *
      * # Arguments
      * * `path` - Path to the executable file
      *
-     * # Returns
+     * # Errors
+     * Returns `NexusError::ProcessStop` if the process is not running, already suspended,
+     * is a native Linux program or suspending fails.
+     */
+    pub fn suspend_exe(&mut self, path: &str) -> Result<()> {
+        if self.is_untracked_program(path) {
+            return Err(NexusError::ProcessStop(format!(
+                "Native Linux programs can't be suspended from the runner: {path}"
+            )));
+        }
+        let Some(child) = self.running_processes.get(path) else {
+            return Err(NexusError::ProcessStop(format!(
+                "Process is not running: {path}"
+            )));
+        };
+        if self.suspended.contains(path) {
+            return Err(NexusError::ProcessStop(format!(
+                "Process is already suspended: {path}"
+            )));
+        }
+
+        process::suspend(child).map_err(|e| {
+            let error_msg = format!("Failed to suspend {path}: {e}");
+            log::error!("{error_msg}");
+            NexusError::ProcessStop(error_msg)
+        })?;
+        self.suspended.insert(path.to_string());
+        self.record_state_change(path, StatusCode::Suspended, None);
+        log::info!("Suspended executable: {path}");
+        Ok(())
+    }
+
+    /**
+     * Resumes a suspended executable.
+     *
+     * # Arguments
+     * * `path` - Path to the executable file
+     *
+     * # Errors
+     * Returns `NexusError::ProcessLaunch` if the process is not suspended or resuming fails.
+     */
+    pub fn resume_exe(&mut self, path: &str) -> Result<()> {
+        let Some(child) = self
+            .running_processes
+            .get(path)
+            .filter(|_| self.suspended.contains(path))
+        else {
+            return Err(NexusError::ProcessLaunch(format!(
+                "Process is not suspended: {path}"
+            )));
+        };
+
+        process::resume(child).map_err(|e| {
+            let error_msg = format!("Failed to resume {path}: {e}");
+            log::error!("{error_msg}");
+            NexusError::ProcessLaunch(error_msg)
+        })?;
+        self.suspended.remove(path);
+        // Failures before the suspension say nothing about the resumed process
+        self.health.remove(path);
+        self.record_state_change(path, self.status_code(path), None);
+        log::info!("Resumed executable: {path}");
+        Ok(())
+    }
+
+    /// Checks whether only the `start.exe` bridge of an executable is tracked
+    fn is_untracked_program(&self, path: &str) -> bool {
+        self.executables
+            .iter()
+            .any(|exe| exe.path == path && !exe.kind.tracks_program())
+    }
+
+    /// Checks if a running executable is suspended
+    pub fn is_suspended(&self, path: &str) -> bool {
+        self.suspended.contains(path)
+    }
+
+    /**
+     * Returns the state reported to other addons. Running executables with a readiness probe
+     * are reported as ready or not ready once it settled, like in the state change events.
+     */
+    pub fn status_code(&self, path: &str) -> StatusCode {
+        let state = self.state(path);
+        if state != ExeState::Running {
+            return state.into();
+        }
+        match self.readiness.get(path).map(|tracker| tracker.state) {
+            Some(ReadyState::Ready) => StatusCode::Ready,
+            Some(ReadyState::Failed) => StatusCode::NotReady,
+            Some(ReadyState::Starting) | None => StatusCode::Running,
+        }
+    }
+
+    /// Returns the lifecycle state of an executable
+    pub fn state(&self, path: &str) -> ExeState {
+        if self.suspended.contains(path) {
+            ExeState::Suspended
+        } else if self.is_running(path) {
+            ExeState::Running
+        } else {
+            ExeState::Stopped
+        }
+    }
+
+    /**
+     * Applies the focus policies of the executables after the game window
+     * lost or regained focus.
+     *
+     * # Arguments
+     * * `focused` - Whether the game window now has focus
+     */
+    pub fn apply_focus_change(&mut self, focused: bool) {
+        let policies: Vec<(String, FocusPolicy, bool)> = self
+            .executables
+            .iter()
+            .filter_map(|exe| {
+                let policy = exe.focus_policy?;
+                // Native programs are only launched, Linux stops and suspends them
+                if !exe.kind.tracks_program() && policy != FocusPolicy::StartOnFocusGain {
+                    return None;
+                }
+                let is_running = self.running_processes.contains_key(&exe.path);
+                Some((exe.path.clone(), policy, is_running))
+            })
+            .collect();
+
+        for (path, policy, is_running) in policies {
+            let result = match (policy, focused, is_running) {
+                (FocusPolicy::StopOnFocusLoss, false, true) => self.stop_exe(&path),
+                (FocusPolicy::StopAndRestart, false, true) => {
+                    self.stopped_on_focus_loss.insert(path.clone());
+                    self.stop_exe(&path)
+                }
+                (FocusPolicy::SuspendAndResume, false, true) if !self.is_suspended(&path) => {
+                    self.suspend_exe(&path)
+                }
+                (FocusPolicy::StartOnFocusGain, true, false) if self.is_enabled_path(&path) => {
+                    self.launch_exe_unattended(&path)
+                }
+                (FocusPolicy::StopAndRestart, true, false)
+                    if self.stopped_on_focus_loss.remove(&path) =>
+                {
+                    self.launch_exe_unattended(&path)
+                }
+                (FocusPolicy::SuspendAndResume, true, true) if self.is_suspended(&path) => {
+                    self.resume_exe(&path)
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                log::warn!("Focus policy failed for {path}: {e}");
+            }
+        }
+    }
+
+    /**
+     * Cleans up finished processes from the running processes map.
+     * Should be called periodically to avoid resource leaks.
+     */
+    pub fn cleanup_finished_processes(&mut self) {
+        let mut finished = Vec::new();
+
+        for (path, child) in &mut self.running_processes {
+            if let Ok(Some(status)) = child.try_wait() {
+                finished.push((path.clone(), status));
+            }
+        }
+
+        for (path, status) in finished {
+            let state = if status.success() {
+                StatusCode::Stopped
+            } else {
+                StatusCode::Crashed
+            };
+            self.record_state_change(&path, state, status.code());
+            let message = match (state, status.code()) {
+                (StatusCode::Stopped, _) => "Exited".to_string(),
+                (_, Some(code)) => format!("Crashed with exit code {code}"),
+                (_, None) => "Crashed".to_string(),
+            };
+            self.add_run_record(&path, message);
+            self.running_processes.remove(&path);
+            self.launched_at.remove(&path);
+            self.suspended.remove(&path);
+            // Reset the is_running flag in the executables vector
+            if let Some(executable) = self.executables.iter_mut().find(|exe| exe.path == path) {
+                executable.is_running = false;
+            }
+            log::info!("Process finished: {path} ({status})");
+        }
+    }
+
+    /**
+     * Checks if an executable is currently running.
+     *
+     * # Arguments
+     * * `path` - Path to the executable file
+     *
+     * # Returns
      * `true` if the process is running, `false` otherwise.
      */
     pub fn is_running(&self, path: &str) -> bool {
@@ -342,13 +1283,25 @@ impl ExeManager {
         for executable in &mut self.executables {
             executable.is_running = false;
         }
+        self.suspended.clear();
+        self.launched_at.clear();
+        self.prompting.clear();
         log::info!("Finished resetting is_running flags");
 
         log::info!(
             "Starting to stop {} running processes",
             self.running_processes.len()
         );
+        let mut stopped = Vec::new();
         for (path, mut child) in self.running_processes.drain() {
+            if self
+                .executables
+                .iter()
+                .any(|exe| exe.path == path && !exe.kind.tracks_program())
+            {
+                log::info!("Leaving native Linux program running: {path}");
+                continue;
+            }
             log::info!(
                 "Attempting to stop process for path: '{}' with PID: {}",
                 path,
@@ -365,8 +1318,13 @@ impl ExeManager {
                     child.id()
                 );
                 let _ = child.wait();
+                stopped.push(path);
             }
         }
+        for path in stopped {
+            self.record_state_change(&path, StatusCode::Stopped, None);
+            self.add_run_record(&path, "Stopped".to_string());
+        }
         log::info!("Finished stopping all processes");
 
         if !errors.is_empty() {
@@ -380,8 +1338,289 @@ impl ExeManager {
         Ok(())
     }
 
+    /// Queues a lifecycle change of an executable for the event API
+    fn record_state_change(&mut self, path: &str, state: StatusCode, exit_code: Option<i32>) {
+        self.revision += 1;
+        let Some(exe) = self.executables.iter().find(|exe| exe.path == path) else {
+            return;
+        };
+        self.state_changes.push(StateChange {
+            id: exe.id,
+            path: path.to_string(),
+            name: exe.display_name().to_string(),
+            state,
+            exit_code,
+        });
+    }
+
+    /// Adds an entry to the run history, dropping the oldest one when full
+    fn add_run_record(&mut self, path: &str, message: String) {
+        let name = self
+            .executables
+            .iter()
+            .find(|exe| exe.path == path)
+            .map_or(path, Executable::display_name)
+            .to_string();
+        if self.run_history.len() == RUN_HISTORY_CAPACITY {
+            self.run_history.pop_front();
+        }
+        self.run_history.push_back(RunRecord {
+            at: SystemTime::now(),
+            name,
+            message,
+        });
+    }
+
+    /// Returns the run history, oldest first
+    pub fn run_history(&self) -> &VecDeque<RunRecord> {
+        &self.run_history
+    }
+
+    /**
+     * Applies the action of a breached resource limit and records why in the run history.
+     * Limits are not enforced while the executable is suspended, and an executable restarted
+     * too often is stopped instead, see [RestartBudget].
+     *
+     * # Arguments
+     * * `path` - Path of the executable whose limit was breached
+     * * `breach` - Limit and usage reported by the monitor
+     *
+     * # Errors
+     * Returns `NexusError::ProcessStop` or `NexusError::ProcessLaunch` if stopping or
+     * launching the executable again fails.
+     */
+    pub fn enforce_limit(&mut self, path: &str, breach: &Breach) -> Result<()> {
+        if !self.running_processes.contains_key(path) || self.suspended.contains(path) {
+            return Ok(());
+        }
+
+        let reason = breach.describe();
+        log::warn!(
+            "Resource limit of {path} breached ({reason}), action: {}",
+            breach.limits.action.label()
+        );
+        match breach.limits.action {
+            LimitAction::Warn => {
+                self.add_run_record(path, format!("Warning: {reason}"));
+                Ok(())
+            }
+            LimitAction::Restart => self.restart_or_stop(path, &reason),
+            LimitAction::Stop => {
+                self.add_run_record(path, format!("Stopped by the runner: {reason}"));
+                self.stop_exe(path)
+            }
+        }
+    }
+
+    /**
+     * Restarts an executable on behalf of the runner. Executables restarted too often are
+     * stopped instead, and so are elevated executables, which would show a UAC prompt the
+     * user did not ask for.
+     *
+     * # Errors
+     * Returns `NexusError::ProcessStop` or `NexusError::ProcessLaunch` if stopping or
+     * launching the executable again fails.
+     */
+    fn restart_or_stop(&mut self, path: &str, reason: &str) -> Result<()> {
+        let refused = if self.is_elevated(path) {
+            Some("elevated executables are not restarted")
+        } else if !self
+            .restarts
+            .entry(path.to_string())
+            .or_default()
+            .try_restart(Instant::now())
+        {
+            Some("restarted too often")
+        } else {
+            None
+        };
+        if let Some(refused) = refused {
+            log::warn!("{path} can't be restarted ({refused}), stopping it instead");
+            self.add_run_record(path, format!("Stopped by the runner, {refused}: {reason}"));
+            return self.stop_exe(path);
+        }
+        self.add_run_record(path, format!("Restarted by the runner: {reason}"));
+        self.stop_exe(path)?;
+        self.launch_exe(path)
+    }
+
+    /// Checks if an executable is launched through the UAC prompt
+    fn is_elevated(&self, path: &str) -> bool {
+        self.executables
+            .iter()
+            .any(|exe| exe.path == path && exe.elevated)
+    }
+
+    /**
+     * Returns the startup state of a running executable. Executables without a
+     * readiness probe are ready as soon as they are spawned.
+     *
+     * # Returns
+     * `None` if the executable is not running.
+     */
+    pub fn ready_state(&self, path: &str) -> Option<ReadyState> {
+        if !self.running_processes.contains_key(path) {
+            return None;
+        }
+        Some(
+            self.readiness
+                .get(path)
+                .map_or(ReadyState::Ready, |tracker| tracker.state),
+        )
+    }
+
+    /// Returns the startup of a running executable whose readiness probe is still running
+    pub fn starting(&self, path: &str) -> Option<&ReadinessTracker> {
+        self.readiness
+            .get(path)
+            .filter(|tracker| tracker.state == ReadyState::Starting)
+            .filter(|_| self.running_processes.contains_key(path))
+    }
+
+    /**
+     * Records the result of a readiness probe, marking the executable failed once its
+     * timeout elapsed. Transitions are added to the run history and reported through
+     * the event API.
+     *
+     * # Arguments
+     * * `path` - Path of the probed executable
+     * * `ready` - Whether the probe succeeded
+     */
+    pub fn record_readiness(&mut self, path: &str, ready: bool) {
+        if !self.running_processes.contains_key(path) {
+            return;
+        }
+        let Some(tracker) = self.readiness.get_mut(path) else {
+            return;
+        };
+        let Some(state) = tracker.update(ready, Instant::now()) else {
+            return;
+        };
+        let elapsed = tracker.started.elapsed().as_secs();
+
+        match state {
+            ReadyState::Ready => {
+                log::info!("{path} is ready after {elapsed}s");
+                self.add_run_record(path, format!("Ready after {elapsed}s"));
+                self.record_state_change(path, StatusCode::Ready, None);
+            }
+            ReadyState::Failed => {
+                log::warn!("{path} did not become ready within {elapsed}s");
+                self.add_run_record(path, format!("Not ready after {elapsed}s"));
+                self.record_state_change(path, StatusCode::NotReady, None);
+            }
+            ReadyState::Starting => {}
+        }
+    }
+
+    /// Returns the health of a running executable, `None` before its first probe
+    pub fn health(&self, path: &str) -> Option<&HealthStatus> {
+        if !self.running_processes.contains_key(path) {
+            return None;
+        }
+        self.health.get(path)
+    }
+
+    /**
+     * Records the result of a health probe, restarting the executable once it failed
+     * as many consecutive times as its health check allows. Results arriving while the
+     * executable is suspended are ignored, and an executable restarted too often is
+     * stopped instead, see [RestartBudget].
+     *
+     * # Arguments
+     * * `path` - Path of the probed executable
+     * * `result` - Result of the probe, the error describing the failure
+     *
+     * # Errors
+     * Returns `NexusError::ProcessStop` or `NexusError::ProcessLaunch` if restarting fails.
+     */
+    pub fn record_health(
+        &mut self,
+        path: &str,
+        result: std::result::Result<(), String>,
+    ) -> Result<()> {
+        if !self.running_processes.contains_key(path) || self.suspended.contains(path) {
+            return Ok(());
+        }
+        let restart_after = self
+            .executables
+            .iter()
+            .find(|exe| exe.path == path)
+            .and_then(|exe| exe.health_check.as_ref())
+            .and_then(|check| check.restart_after);
+
+        let previous = self.health.get(path);
+        let was_unhealthy = previous.is_some_and(|status| status.failures() > 0);
+        let status = HealthStatus::next(previous, result);
+
+        let mut restart = None;
+        match &status {
+            HealthStatus::Healthy if was_unhealthy => {
+                log::info!("{path} is healthy again");
+                self.add_run_record(path, "Healthy again".to_string());
+            }
+            HealthStatus::Healthy => {}
+            HealthStatus::Unhealthy { failures, error } => {
+                log::warn!("Health check of {path} failed ({failures} in a row): {error}");
+                if *failures == 1 {
+                    self.add_run_record(path, format!("Unhealthy: {error}"));
+                }
+                if restart_after.is_some_and(|limit| *failures >= limit) {
+                    restart = Some(format!(
+                        "health check failed {failures} times in a row: {error}"
+                    ));
+                }
+            }
+        }
+        self.health.insert(path.to_string(), status);
+
+        match restart {
+            Some(reason) => self.restart_or_stop(path, &reason),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns a counter incremented on every process lifecycle change
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns the process id of a running executable, `None` for native Linux programs
+    pub fn pid(&self, path: &str) -> Option<u32> {
+        if self.is_untracked_program(path) {
+            return None;
+        }
+        self.running_processes.get(path).map(RunningProcess::id)
+    }
+
+    /**
+     * Returns the process ids of a running executable: its process and the ones it started,
+     * like the interpreter of a script and the programs the script launched.
+     * Empty for native Linux programs.
+     */
+    pub fn pids(&self, path: &str) -> Vec<u32> {
+        if self.is_untracked_program(path) {
+            return Vec::new();
+        }
+        self.running_processes
+            .get(path)
+            .map(RunningProcess::pids)
+            .unwrap_or_default()
+    }
+
+    /// Returns when a running executable was launched
+    pub fn launched_at(&self, path: &str) -> Option<SystemTime> {
+        self.launched_at.get(path).copied()
+    }
+
+    /// Returns and clears the lifecycle changes recorded since the last call
+    pub fn take_state_changes(&mut self) -> Vec<StateChange> {
+        std::mem::take(&mut self.state_changes)
+    }
+
     /**
      * Gets the number of running processes.
+     * Links are not tracked so they are never counted.
      *
      * # Returns
      * Number of currently running processes.
@@ -392,6 +1631,14 @@ impl ExeManager {
 
     // Additional settings helpers
 
+    /**
+     * Sets the startup flag of an executable, in the active profile if there is one.
+     * Whether the executable is enabled in the profile is left unchanged.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the index is invalid, the executable is disabled
+     * in the active profile or saving fails.
+     */
     pub(crate) fn set_launch_on_startup(&mut self, index: usize, value: bool) -> Result<()> {
         if index >= self.executables.len() {
             return Err(NexusError::FileOperation(format!(
@@ -400,20 +1647,1160 @@ impl ExeManager {
                 self.executables.len()
             )));
         }
-        self.executables[index].launch_on_startup = value;
+        self.check_launch_on_startup(index, value)?;
+        self.store_launch_on_startup(index, value);
         self.save_exe_list()
     }
-}
 
-/// Opens a file dialog to select an executable file
-pub fn open_file_dialog() -> Option<String> {
-    rfd::FileDialog::new()
-        .add_filter("Executable Files", &["exe"])
-        .add_filter("All Files", &["*"])
-        .set_title("Select Executable")
-        .pick_file()
-        .map(|path| path.to_string_lossy().to_string())
-}
+    /// Refuses to launch an executable on startup when the active profile disables it
+    fn check_launch_on_startup(&self, index: usize, value: bool) -> Result<()> {
+        let exe = &self.executables[index];
+        match self.active_profile() {
+            Some(profile) if value && !self.is_enabled(exe) => {
+                Err(NexusError::FileOperation(format!(
+                    "{} is disabled in profile {}, enable it to launch it on startup",
+                    exe.display_name(),
+                    profile.name
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
 
-/// Global static reference to the exe manager
-pub static EXE_MANAGER: std::sync::OnceLock<Arc<Mutex<ExeManager>>> = std::sync::OnceLock::new();
+    /// Stores the startup flag in the profile entry, or in the executable without profile
+    fn store_launch_on_startup(&mut self, index: usize, value: bool) {
+        let path = self.executables[index].path.clone();
+        let active_profile = self.active_profile.clone();
+        match active_profile.and_then(|name| self.profiles.iter_mut().find(|p| p.name == name)) {
+            Some(profile) => {
+                if let Some(entry) = profile.entries.iter_mut().find(|e| e.path == path) {
+                    entry.launch_on_startup = value;
+                }
+            }
+            None => self.executables[index].launch_on_startup = value,
+        }
+    }
+
+    /// Returns the startup flag of an executable, as seen by the active profile if there is one
+    pub fn launch_on_startup(&self, exe: &Executable) -> bool {
+        match self.active_profile() {
+            Some(profile) => profile
+                .entry(&exe.path)
+                .is_some_and(|entry| entry.launch_on_startup),
+            None => exe.launch_on_startup,
+        }
+    }
+
+    fn is_enabled_path(&self, path: &str) -> bool {
+        self.executables
+            .iter()
+            .find(|exe| exe.path == path)
+            .is_some_and(|exe| self.is_enabled(exe))
+    }
+
+    /// Returns whether an executable is enabled in the active profile.
+    /// Every executable is enabled when no profile is active.
+    pub fn is_enabled(&self, exe: &Executable) -> bool {
+        self.active_profile()
+            .is_none_or(|profile| profile.entry(&exe.path).is_some())
+    }
+
+    /**
+     * Returns the paths of the executables that should be launched on addon load,
+     * either because of their own setting or because their group is marked for startup.
+     * Only executables enabled in the active profile are considered.
+     */
+    pub fn startup_paths(&self) -> Vec<String> {
+        self.executables
+            .iter()
+            .filter(|exe| !exe.is_running && self.is_enabled(exe))
+            .filter(|exe| {
+                self.launch_on_startup(exe)
+                    || exe.group.as_deref().is_some_and(|name| {
+                        self.groups
+                            .iter()
+                            .any(|group| group.name == name && group.launch_on_startup)
+                    })
+            })
+            .map(|exe| exe.path.clone())
+            .collect()
+    }
+
+    /**
+     * Applies the map triggers of every executable enabled in the active profile
+     * after the player changed map.
+     *
+     * # Arguments
+     * * `previous` - Map before the change, `None` on the first evaluation
+     * * `current` - Map after the change
+     */
+    pub fn apply_map_triggers(&mut self, previous: Option<&MapSnapshot>, current: &MapSnapshot) {
+        let decisions: Vec<(String, TriggerAction)> = self
+            .executables
+            .iter()
+            .filter(|exe| self.is_enabled(exe))
+            .map(|exe| {
+                let is_running = self.running_processes.contains_key(&exe.path);
+                let is_suspended = self.suspended.contains(&exe.path);
+                let action = match triggers::decide(
+                    &exe.map_triggers,
+                    previous,
+                    current,
+                    is_running,
+                    is_suspended,
+                ) {
+                    // Only Linux can stop or suspend a native program
+                    TriggerAction::Stop | TriggerAction::Suspend if !exe.kind.tracks_program() => {
+                        TriggerAction::Nothing
+                    }
+                    action => action,
+                };
+                (exe.path.clone(), action)
+            })
+            .collect();
+
+        for (path, action) in decisions {
+            let result = match action {
+                TriggerAction::Launch => {
+                    log::info!("Map trigger launching {path} on map {}", current.map_id);
+                    self.launch_exe_unattended(&path)
+                }
+                TriggerAction::Stop => {
+                    log::info!("Map trigger stopping {path} on map {}", current.map_id);
+                    self.stop_exe(&path)
+                }
+                TriggerAction::Suspend => {
+                    log::info!("Map trigger suspending {path} on map {}", current.map_id);
+                    self.suspend_exe(&path)
+                }
+                TriggerAction::Resume => {
+                    log::info!("Map trigger resuming {path} on map {}", current.map_id);
+                    self.resume_exe(&path)
+                }
+                TriggerAction::Nothing => Ok(()),
+            };
+            if let Err(e) = result {
+                log::warn!("Map trigger failed for {path}: {e}");
+            }
+        }
+    }
+
+    /**
+     * Launches and stops the executables enabled in the active profile whose
+     * `launch_on` / `stop_on` transition matches the one that just happened.
+     */
+    pub fn apply_game_event(&mut self, event: GameEvent) {
+        let to_launch: Vec<String> = self
+            .executables
+            .iter()
+            .filter(|exe| exe.launch_on == Some(event) && self.is_enabled(exe))
+            .filter(|exe| !self.running_processes.contains_key(&exe.path))
+            .map(|exe| exe.path.clone())
+            .collect();
+        let to_stop: Vec<String> = self
+            .executables
+            .iter()
+            .filter(|exe| exe.stop_on == Some(event))
+            .filter(|exe| self.running_processes.contains_key(&exe.path))
+            .map(|exe| exe.path.clone())
+            .collect();
+
+        for path in to_stop {
+            log::info!("Stopping {path} on {event:?}");
+            if let Err(e) = self.stop_exe(&path) {
+                log::warn!("Game transition trigger failed for {path}: {e}");
+            }
+        }
+        for path in to_launch {
+            log::info!("Launching {path} on {event:?}");
+            if let Err(e) = self.launch_exe_unattended(&path) {
+                log::warn!("Game transition trigger failed for {path}: {e}");
+            }
+        }
+    }
+
+    // Profile helpers
+
+    /**
+     * Adds a new profile, initialized with every executable and its current startup flag.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the name is empty, already used, or saving fails.
+     */
+    pub fn add_profile(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(NexusError::FileOperation(
+                "Cannot add profile with an empty name".to_string(),
+            ));
+        }
+        if self.profiles.iter().any(|profile| profile.name == name) {
+            return Err(NexusError::FileOperation(format!(
+                "Profile already exists: {name}"
+            )));
+        }
+
+        let entries = self
+            .executables
+            .iter()
+            .map(|exe| ProfileEntry {
+                path: exe.path.clone(),
+                launch_on_startup: self.launch_on_startup(exe),
+            })
+            .collect();
+        self.profiles.push(Profile {
+            name: name.to_string(),
+            entries,
+            characters: Vec::new(),
+            professions: Vec::new(),
+            accounts: Vec::new(),
+        });
+        self.save_exe_list()?;
+        log::info!("Added profile: {name}");
+        Ok(())
+    }
+
+    /**
+     * Removes a profile. If it was active, every executable becomes enabled again.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the profile does not exist or saving fails.
+     */
+    pub fn remove_profile(&mut self, name: &str) -> Result<()> {
+        let Some(position) = self.profiles.iter().position(|p| p.name == name) else {
+            return Err(NexusError::FileOperation(format!(
+                "Profile does not exist: {name}"
+            )));
+        };
+
+        self.profiles.remove(position);
+        if self.active_profile.as_deref() == Some(name) {
+            self.active_profile = None;
+        }
+        self.save_exe_list()?;
+        log::info!("Removed profile: {name}");
+        Ok(())
+    }
+
+    /**
+     * Enables or disables an executable in the active profile.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if no profile is active, the index is invalid or saving fails.
+     */
+    pub fn set_enabled_in_profile(&mut self, index: usize, enabled: bool) -> Result<()> {
+        let Some(path) = self.executables.get(index).map(|exe| exe.path.clone()) else {
+            return Err(NexusError::FileOperation(format!(
+                "Index out of bounds: {} >= {}",
+                index,
+                self.executables.len()
+            )));
+        };
+        let active_profile = self.active_profile.clone();
+        let Some(profile) =
+            active_profile.and_then(|name| self.profiles.iter_mut().find(|p| p.name == name))
+        else {
+            return Err(NexusError::FileOperation(
+                "No active profile to update".to_string(),
+            ));
+        };
+
+        profile.entries.retain(|entry| entry.path != path);
+        if enabled {
+            profile.entries.push(ProfileEntry {
+                path,
+                launch_on_startup: false,
+            });
+        }
+        self.save_exe_list()
+    }
+
+    /**
+     * Replaces the automatic selection bindings of a profile.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the profile does not exist or saving fails.
+     */
+    pub fn set_profile_bindings(
+        &mut self,
+        name: &str,
+        characters: Vec<String>,
+        professions: Vec<u32>,
+        accounts: Vec<String>,
+    ) -> Result<()> {
+        let Some(profile) = self.profiles.iter_mut().find(|p| p.name == name) else {
+            return Err(NexusError::FileOperation(format!(
+                "Profile does not exist: {name}"
+            )));
+        };
+        profile.characters = characters;
+        profile.professions = professions;
+        profile.accounts = accounts;
+        self.save_exe_list()
+    }
+
+    /**
+     * Finds the profile bound to a logged in identity.
+     * Character bindings take precedence over profession bindings, which take precedence
+     * over account bindings. Names are compared case-insensitively.
+     *
+     * # Returns
+     * The name of the bound profile, or `None` if no profile is bound to the identity.
+     */
+    pub fn profile_for_identity(
+        &self,
+        character: &str,
+        profession: u32,
+        account: Option<&str>,
+    ) -> Option<&str> {
+        let by_character = self.profiles.iter().find(|profile| {
+            profile
+                .characters
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(character))
+        });
+        let by_profession = || {
+            self.profiles
+                .iter()
+                .find(|profile| profile.professions.contains(&profession))
+        };
+        let by_account = || {
+            let account = account?;
+            self.profiles.iter().find(|profile| {
+                profile
+                    .accounts
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(account))
+            })
+        };
+
+        by_character
+            .or_else(by_profession)
+            .or_else(by_account)
+            .map(|profile| profile.name.as_str())
+    }
+
+    /**
+     * Switches to another profile, `None` enabling every executable.
+     * Running executables that are not enabled in the new profile are stopped, except native
+     * Linux programs which only Linux can stop, and the profile's startup executables that
+     * are not running are launched, except elevated ones.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the profile does not exist or saving fails,
+     * `NexusError::ProcessLaunch` if some executables failed to stop or launch.
+     */
+    pub fn switch_profile(&mut self, name: Option<&str>) -> Result<()> {
+        if let Some(name) = name {
+            if !self.profiles.iter().any(|profile| profile.name == name) {
+                return Err(NexusError::FileOperation(format!(
+                    "Profile does not exist: {name}"
+                )));
+            }
+        }
+        if self.active_profile.as_deref() == name {
+            return Ok(());
+        }
+
+        self.active_profile = name.map(str::to_string);
+        self.save_exe_list()?;
+        log::info!("Switched to profile: {}", name.unwrap_or("All executables"));
+
+        let to_stop: Vec<String> = self
+            .executables
+            .iter()
+            .filter(|exe| !self.is_enabled(exe) && self.running_processes.contains_key(&exe.path))
+            .filter(|exe| exe.kind.tracks_program())
+            .map(|exe| exe.path.clone())
+            .collect();
+        // Elevated executables only launch on startup, a switch would show their UAC prompt unasked
+        let to_launch: Vec<String> = self
+            .startup_paths()
+            .into_iter()
+            .filter(|path| !self.is_elevated(path))
+            .collect();
+
+        let mut errors: Vec<String> = to_stop
+            .iter()
+            .filter_map(|path| self.stop_exe(path).err())
+            .map(|e| e.to_string())
+            .collect();
+        errors.extend(
+            to_launch
+                .iter()
+                .filter_map(|path| self.launch_exe(path).err())
+                .map(|e| e.to_string()),
+        );
+
+        if !errors.is_empty() {
+            return Err(NexusError::ProcessLaunch(format!(
+                "Failed to switch some processes: {}",
+                errors.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    // Group helpers
+
+    /**
+     * Adds a new empty group and persists it.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the name is empty, already used, or saving fails.
+     */
+    pub fn add_group(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(NexusError::FileOperation(
+                "Cannot add group with an empty name".to_string(),
+            ));
+        }
+        if self.groups.iter().any(|group| group.name == name) {
+            return Err(NexusError::FileOperation(format!(
+                "Group already exists: {name}"
+            )));
+        }
+
+        self.groups.push(Group {
+            name: name.to_string(),
+            launch_on_startup: false,
+        });
+        self.save_exe_list()?;
+        log::info!("Added group: {name}");
+        Ok(())
+    }
+
+    /**
+     * Removes a group. Its executables are kept and become ungrouped.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the group does not exist or saving fails.
+     */
+    pub fn remove_group(&mut self, name: &str) -> Result<()> {
+        let Some(position) = self.groups.iter().position(|group| group.name == name) else {
+            return Err(NexusError::FileOperation(format!(
+                "Group does not exist: {name}"
+            )));
+        };
+
+        self.groups.remove(position);
+        for exe in &mut self.executables {
+            if exe.group.as_deref() == Some(name) {
+                exe.group = None;
+            }
+        }
+        self.save_exe_list()?;
+        log::info!("Removed group: {name}");
+        Ok(())
+    }
+
+    pub(crate) fn set_group_launch_on_startup(&mut self, name: &str, value: bool) -> Result<()> {
+        let Some(group) = self.groups.iter_mut().find(|group| group.name == name) else {
+            return Err(NexusError::FileOperation(format!(
+                "Group does not exist: {name}"
+            )));
+        };
+        group.launch_on_startup = value;
+        self.save_exe_list()
+    }
+
+    /**
+     * Moves an executable into a group, or out of any group when `group` is `None`.
+     *
+     * # Errors
+     * Returns `NexusError::FileOperation` if the index or group is invalid or saving fails.
+     */
+    pub fn set_exe_group(&mut self, index: usize, group: Option<String>) -> Result<()> {
+        if index >= self.executables.len() {
+            return Err(NexusError::FileOperation(format!(
+                "Index out of bounds: {} >= {}",
+                index,
+                self.executables.len()
+            )));
+        }
+        if let Some(name) = &group {
+            if !self.groups.iter().any(|g| &g.name == name) {
+                return Err(NexusError::FileOperation(format!(
+                    "Group does not exist: {name}"
+                )));
+            }
+        }
+        self.executables[index].group = group;
+        self.save_exe_list()
+    }
+
+    /**
+     * Launches every executable of a group that is not already running.
+     * `None` targets the ungrouped executables.
+     *
+     * # Errors
+     * Returns `NexusError::ProcessLaunch` if any executable fails to launch.
+     */
+    pub fn launch_group(&mut self, group: Option<&str>) -> Result<()> {
+        let paths: Vec<String> = self
+            .executables
+            .iter()
+            .filter(|exe| exe.group.as_deref() == group && !exe.is_running)
+            .map(|exe| exe.path.clone())
+            .collect();
+
+        let errors: Vec<String> = paths
+            .iter()
+            .filter_map(|path| self.launch_exe(path).err())
+            .map(|e| e.to_string())
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(NexusError::ProcessLaunch(format!(
+                "Failed to launch some processes: {}",
+                errors.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /**
+     * Stops every running executable of a group, leaving native Linux programs running.
+     * `None` targets the ungrouped executables.
+     *
+     * # Errors
+     * Returns `NexusError::ProcessStop` if any executable fails to stop.
+     */
+    pub fn stop_group(&mut self, group: Option<&str>) -> Result<()> {
+        let paths: Vec<String> = self
+            .executables
+            .iter()
+            .filter(|exe| exe.group.as_deref() == group)
+            .filter(|exe| self.running_processes.contains_key(&exe.path))
+            .filter(|exe| exe.kind.tracks_program())
+            .map(|exe| exe.path.clone())
+            .collect();
+
+        let errors: Vec<String> = paths
+            .iter()
+            .filter_map(|path| self.stop_exe(path).err())
+            .map(|e| e.to_string())
+            .collect();
+
+        if !errors.is_empty() {
+            return Err(NexusError::ProcessStop(format!(
+                "Failed to stop some processes: {}",
+                errors.join(", ")
+            )));
+        }
+        Ok(())
+    }
+}
+
+/**
+ * Normalizes a path typed or pasted by the user. Under wine, Unix paths and `~` are
+ * translated to `Z:` drive paths for Windows programs, and `Z:` drive paths back to
+ * Unix paths for native Linux programs.
+ */
+fn normalize_exe_path(path: &str, kind: ExeKind) -> String {
+    if !wine::is_wine() {
+        return path.trim().trim_matches('"').to_string();
+    }
+
+    let home = wine::unix_home();
+    match kind {
+        ExeKind::Windows => wine_path::normalize(path, home.as_deref()),
+        ExeKind::LinuxNative => wine_path::normalize_unix(path, home.as_deref()),
+        ExeKind::Url => path.trim().trim_matches('"').to_string(),
+    }
+}
+
+/// Checks that a path is non-empty and points to an existing file
+fn validate_exe_path(path: &str, kind: ExeKind) -> Result<()> {
+    if path.trim().is_empty() {
+        return Err(NexusError::FileOperation(
+            "Cannot add empty executable path".to_string(),
+        ));
+    }
+
+    if kind == ExeKind::Url {
+        return if is_url(path) {
+            Ok(())
+        } else {
+            Err(NexusError::FileOperation(format!(
+                "Link must start with a scheme such as https:// or steam://: {path}"
+            )))
+        };
+    }
+
+    // Unix paths can't be checked from the Windows side
+    if kind == ExeKind::LinuxNative {
+        if !wine::is_wine() {
+            return Err(NexusError::FileOperation(
+                "Native Linux programs can only be launched under wine".to_string(),
+            ));
+        }
+        if !path.starts_with('/') {
+            return Err(NexusError::FileOperation(format!(
+                "Native Linux program path must be an absolute Unix path: {path}"
+            )));
+        }
+        return Ok(());
+    }
+
+    let pb = PathBuf::from(path);
+    if !pb.exists() || !pb.is_file() {
+        return Err(NexusError::FileOperation(format!(
+            "Executable path does not exist or is not a file: {}",
+            path
+        )));
+    }
+    Ok(())
+}
+
+/**
+ * Builds the command spawning an executable, going through the interpreter of scripts and
+ * the target of shortcuts.
+ *
+ * # Errors
+ * Returns `NexusError::ProcessLaunch` if a shortcut can't be resolved, or a native Linux
+ * program is launched outside of wine.
+ */
+fn build_command(path: &str, executable: &Executable) -> Result<Command> {
+    use std::os::windows::process::CommandExt;
+
+    // CREATE_NO_WINDOW | DETACHED_PROCESS to avoid inheriting console
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    const DETACHED_PROCESS: u32 = 0x00000008;
+    // Windows programs are put in their job before they run, see `process::spawn_in_job`
+    const CREATE_SUSPENDED: u32 = 0x00000004;
+    let mut creation_flags = CREATE_NO_WINDOW | DETACHED_PROCESS;
+    let mut command = match executable.kind {
+        ExeKind::Windows => {
+            // Scripts and shortcuts go through their interpreter or target
+            let plan = launch::plan(path, &executable.args).map_err(|e| {
+                log::error!("{e}");
+                NexusError::ProcessLaunch(e)
+            })?;
+            let mut command = Command::new(&plan.program);
+            command.args(&plan.args);
+            if !plan.raw_args.is_empty() {
+                command.raw_arg(&plan.raw_args);
+            }
+            if let Some(working_dir) = &plan.working_dir {
+                command.current_dir(working_dir);
+            }
+            creation_flags |= CREATE_SUSPENDED;
+            command
+        }
+        ExeKind::Url => unreachable!("links are opened above"),
+        ExeKind::LinuxNative => {
+            if !wine::is_wine() {
+                return Err(NexusError::ProcessLaunch(format!(
+                    "Native Linux programs can only be launched under wine: {path}"
+                )));
+            }
+            let mut command = wine::unix_command(path);
+            if !executable.args.trim().is_empty() {
+                command.raw_arg(&executable.args);
+            }
+            command
+        }
+    };
+    command
+        .creation_flags(creation_flags)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    if !executable.working_dir.trim().is_empty() {
+        command.current_dir(&executable.working_dir);
+    }
+    command.envs(&executable.env);
+    Ok(command)
+}
+
+/**
+ * Refuses the settings of a native Linux program that need control over its process.
+ * Only the `start.exe` bridge is tracked: stopping it leaves the program running, and
+ * suspending, measuring or finding the windows of the bridge says nothing about the program.
+ *
+ * # Errors
+ * Returns `NexusError::FileOperation` naming the first setting that can't be used.
+ */
+fn validate_native(executable: &Executable) -> Result<()> {
+    let unsupported = if executable.elevated {
+        Some("launched elevated")
+    } else if executable.stop_on.is_some()
+        || executable.map_triggers.stop_on_exit
+        || executable.map_triggers.suspend_on_exit
+    {
+        Some("stopped or suspended by a trigger")
+    } else if executable
+        .focus_policy
+        .is_some_and(|policy| policy != FocusPolicy::StartOnFocusGain)
+    {
+        Some("stopped or suspended on focus loss")
+    } else if !executable.priority.is_normal() || executable.affinity != 0 {
+        Some("given a priority or CPU affinity")
+    } else if executable.limits.is_some() {
+        Some("given resource limits")
+    } else if executable
+        .health_check
+        .as_ref()
+        .is_some_and(|check| check.restart_after.is_some())
+    {
+        Some("restarted by a health check")
+    } else if executable.readiness.is_some() {
+        Some("given a readiness probe")
+    } else {
+        None
+    };
+    match unsupported {
+        Some(what) => Err(NexusError::FileOperation(format!(
+            "Native Linux programs can't be {what}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/**
+ * Refuses the settings of an elevated executable the UAC prompt can't honour. It starts the
+ * process from the user's environment, and must only show up when the user asks for a launch:
+ * from the list, the quick access menu, a keybind or on startup. Settings launching the
+ * executable by itself are refused, see [ExeManager::launch_exe_unattended] for the launches
+ * that can't be configured here.
+ *
+ * # Errors
+ * Returns `NexusError::FileOperation` naming the first setting that can't be used.
+ */
+fn validate_elevated(executable: &Executable) -> Result<()> {
+    let unsupported = if !executable.env.is_empty() {
+        Some("given environment variables")
+    } else if executable
+        .limits
+        .as_ref()
+        .is_some_and(|limits| limits.action == LimitAction::Restart)
+    {
+        Some("restarted by a resource limit")
+    } else if executable
+        .health_check
+        .as_ref()
+        .is_some_and(|check| check.restart_after.is_some())
+    {
+        Some("restarted by a health check")
+    } else if matches!(
+        executable.focus_policy,
+        Some(FocusPolicy::StartOnFocusGain | FocusPolicy::StopAndRestart)
+    ) {
+        Some("launched on focus gain")
+    } else if executable.schedule.is_some() {
+        Some("launched on a schedule")
+    } else if !executable.map_triggers.conditions.is_empty() {
+        Some("launched by a map trigger")
+    } else if executable.launch_on.is_some() {
+        Some("launched on a game transition")
+    } else {
+        None
+    };
+    match unsupported {
+        Some(what) => Err(NexusError::FileOperation(format!(
+            "Elevated executables can't be {what}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/**
+ * Plans the elevated launch of an executable, its working directory overriding the one of
+ * a shortcut like for spawned executables.
+ *
+ * # Errors
+ * Returns `NexusError::ProcessLaunch` if the executable is not a Windows program or a
+ * shortcut can't be resolved.
+ */
+fn elevated_plan(path: &str, executable: &Executable) -> Result<launch::LaunchPlan> {
+    if executable.kind != ExeKind::Windows {
+        return Err(NexusError::ProcessLaunch(format!(
+            "Only Windows programs can be launched elevated: {path}"
+        )));
+    }
+    let mut plan = launch::plan(path, &executable.args).map_err(|e| {
+        log::error!("{e}");
+        NexusError::ProcessLaunch(e)
+    })?;
+    if !executable.working_dir.trim().is_empty() {
+        plan.working_dir = Some(executable.working_dir.clone());
+    }
+    Ok(plan)
+}
+
+/**
+ * Applies the priority and CPU affinity of an executable to its freshly spawned process.
+ * Failures are logged and the process keeps its inherited settings.
+ */
+fn apply_scheduling(process: &RunningProcess, executable: &Executable) {
+    if !executable.priority.is_normal() {
+        if let Err(e) = process::set_priority(process, executable.priority) {
+            log::warn!(
+                "Failed to set the priority of {} to {}: {e}",
+                executable.path,
+                executable.priority.label()
+            );
+        }
+    }
+    if executable.affinity != 0 {
+        if let Err(e) = process::set_affinity(process, executable.affinity) {
+            log::warn!(
+                "Failed to set the CPU affinity of {} to {:#x}: {e}",
+                executable.path,
+                executable.affinity
+            );
+        }
+    }
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// Checks that a link starts with a URL scheme, single letters being drive letters
+fn is_url(link: &str) -> bool {
+    let Some((scheme, rest)) = link.split_once(':') else {
+        return false;
+    };
+    scheme.len() > 1
+        && !rest.is_empty()
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+/// Opens a file dialog to select an executable file
+pub fn open_file_dialog() -> Option<String> {
+    rfd::FileDialog::new()
+        .add_filter(
+            "Executables, scripts and shortcuts",
+            &launch::LAUNCHABLE_EXTENSIONS,
+        )
+        .add_filter("All Files", &["*"])
+        .set_title("Select Executable")
+        .pick_file()
+        .map(|path| path.to_string_lossy().to_string())
+}
+
+/// Global static reference to the exe manager
+pub static EXE_MANAGER: std::sync::OnceLock<Arc<Mutex<ExeManager>>> = std::sync::OnceLock::new();
+
+/// Threads showing the UAC prompt of elevated launches
+static ELEVATION_PROMPTS: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Waits for the UAC prompts still shown, must be called without holding the manager lock
+pub fn wait_for_elevation_prompts() {
+    let prompts = ELEVATION_PROMPTS
+        .lock()
+        .map(|mut prompts| std::mem::take(&mut *prompts))
+        .unwrap_or_default();
+    for prompt in prompts {
+        let _ = prompt.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::addon::{
+        health::HealthProbe,
+        triggers::{GameMode, TriggerCondition},
+    };
+    use std::path::Path;
+
+    /// Empty addon directory, unique to the test
+    fn temp_addon_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("exe-runner-manager-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Manager with one link entry per name, links need no file on disk
+    fn manager_with_links(dir: &Path, names: &[&str]) -> ExeManager {
+        let mut manager = ExeManager::new(dir.to_path_buf()).unwrap();
+        for name in names {
+            manager
+                .add_exe_of_kind(format!("https://{name}"), ExeKind::Url)
+                .unwrap();
+        }
+        manager
+    }
+
+    fn order(manager: &ExeManager) -> Vec<&str> {
+        manager
+            .executables()
+            .iter()
+            .map(|exe| exe.path.trim_start_matches("https://"))
+            .collect()
+    }
+
+    #[test]
+    fn executables_move_between_groups() {
+        let dir = temp_addon_dir("groups");
+        let mut manager = manager_with_links(&dir, &["a", "b"]);
+        manager.add_group("Overlays").unwrap();
+
+        manager.set_exe_group(1, Some("Overlays".into())).unwrap();
+        assert_eq!(manager.executables()[1].group.as_deref(), Some("Overlays"));
+        assert!(manager.set_exe_group(0, Some("Missing".into())).is_err());
+        assert!(manager.set_exe_group(2, None).is_err());
+        assert_eq!(manager.executables()[0].group, None);
+
+        // Persisted
+        let reloaded = ExeManager::new(dir.clone()).unwrap();
+        assert_eq!(reloaded.executables()[1].group.as_deref(), Some("Overlays"));
+
+        manager.set_exe_group(1, None).unwrap();
+        assert_eq!(manager.executables()[1].group, None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn move_lands_on_the_target_index() {
+        let dir = temp_addon_dir("reorder");
+        let mut manager = manager_with_links(&dir, &["a", "b", "c", "d"]);
+
+        // Up: the moved entry takes the target's place, the others shift down
+        manager.move_exe(2, 0).unwrap();
+        assert_eq!(order(&manager), ["c", "a", "b", "d"]);
+        // Down: the entries in between shift up
+        manager.move_exe(0, 2).unwrap();
+        assert_eq!(order(&manager), ["a", "b", "c", "d"]);
+        // To the end
+        manager.move_exe(1, 3).unwrap();
+        assert_eq!(order(&manager), ["a", "c", "d", "b"]);
+        // Swapping neighbours, like the arrow buttons do
+        manager.move_exe(3, 2).unwrap();
+        assert_eq!(order(&manager), ["a", "c", "b", "d"]);
+        manager.move_exe(1, 1).unwrap();
+        assert_eq!(order(&manager), ["a", "c", "b", "d"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn startup_flag_follows_the_active_profile() {
+        let dir = temp_addon_dir("startup");
+        let mut manager = manager_with_links(&dir, &["a", "b"]);
+        manager.add_profile("Raids").unwrap();
+        manager.switch_profile(Some("Raids")).unwrap();
+        manager.set_enabled_in_profile(1, false).unwrap();
+
+        manager.set_launch_on_startup(0, true).unwrap();
+        assert!(manager.launch_on_startup(&manager.executables()[0]));
+        assert!(!manager.executables()[0].launch_on_startup);
+
+        // Disabled executables stay disabled
+        assert!(manager.set_launch_on_startup(1, true).is_err());
+        assert!(!manager.is_enabled(&manager.executables()[1]));
+
+        // The edit modal goes through the same profile entry
+        let mut updated = manager.executables()[0].clone();
+        updated.launch_on_startup = false;
+        manager.update_exe(0, updated).unwrap();
+        assert!(!manager.launch_on_startup(&manager.executables()[0]));
+        let mut updated = manager.executables()[1].clone();
+        updated.launch_on_startup = true;
+        assert!(manager.update_exe(1, updated).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn ids_of_removed_executables_are_not_reused() {
+        let dir = temp_addon_dir("ids");
+        let mut manager = manager_with_links(&dir, &["a", "b"]);
+        manager.executables.pop();
+        manager.save_exe_list().unwrap();
+
+        let mut reloaded = ExeManager::new(dir.clone()).unwrap();
+        let id = reloaded
+            .add_exe_of_kind("https://c".to_string(), ExeKind::Url)
+            .unwrap();
+        assert_eq!(id, 3);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn renamed_executables_keep_their_tracked_state() {
+        let dir = temp_addon_dir("rename");
+        let mut manager = manager_with_links(&dir, &["a"]);
+        let readiness = Readiness::new(crate::addon::readiness::ReadinessProbe::Window);
+        manager.readiness.insert(
+            "https://a".to_string(),
+            ReadinessTracker::new(&readiness, Instant::now()),
+        );
+        manager.health.insert(
+            "https://a".to_string(),
+            HealthStatus::Unhealthy {
+                failures: 2,
+                error: "Connection refused".to_string(),
+            },
+        );
+        manager
+            .restarts
+            .insert("https://a".to_string(), RestartBudget::default());
+        manager
+            .stopped_on_focus_loss
+            .insert("https://a".to_string());
+        manager.prompting.insert("https://a".to_string());
+
+        let mut updated = manager.executables()[0].clone();
+        updated.path = "https://b".to_string();
+        manager.update_exe(0, updated).unwrap();
+
+        assert_eq!(
+            manager
+                .readiness
+                .get("https://b")
+                .map(|tracker| tracker.state),
+            Some(ReadyState::Starting)
+        );
+        assert!(manager.health.contains_key("https://b"));
+        assert!(manager.restarts.contains_key("https://b"));
+        assert!(manager.stopped_on_focus_loss.contains("https://b"));
+        assert!(manager.is_prompting("https://b"));
+        assert!(!manager.readiness.contains_key("https://a"));
+        assert!(!manager.health.contains_key("https://a"));
+        assert!(!manager.is_prompting("https://a"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn native_programs_refuse_settings_needing_their_process() {
+        let native = Executable {
+            path: "/usr/bin/obs".to_string(),
+            kind: ExeKind::LinuxNative,
+            focus_policy: Some(FocusPolicy::StartOnFocusGain),
+            ..Default::default()
+        };
+        assert!(validate_native(&native).is_ok());
+
+        let stopped_on_focus_loss = Executable {
+            focus_policy: Some(FocusPolicy::StopOnFocusLoss),
+            ..native.clone()
+        };
+        let stopped_on_exit = Executable {
+            stop_on: Some(GameEvent::ReturnedToCharacterSelect),
+            ..native.clone()
+        };
+        let mut suspended_on_exit = native.clone();
+        suspended_on_exit.map_triggers.suspend_on_exit = true;
+        let prioritized = Executable {
+            priority: Priority::Idle,
+            ..native.clone()
+        };
+        let elevated = Executable {
+            elevated: true,
+            ..native
+        };
+        for executable in [
+            stopped_on_focus_loss,
+            stopped_on_exit,
+            suspended_on_exit,
+            prioritized,
+            elevated,
+        ] {
+            assert!(validate_native(&executable).is_err());
+        }
+    }
+
+    #[test]
+    fn elevated_executables_are_only_launched_on_request() {
+        let elevated = Executable {
+            path: "C:\\Tools\\tool.exe".to_string(),
+            elevated: true,
+            launch_on_startup: true,
+            focus_policy: Some(FocusPolicy::StopOnFocusLoss),
+            limits: Some(ResourceLimits {
+                max_memory_mb: Some(1500),
+                max_cpu_percent: None,
+                duration_secs: 60,
+                action: LimitAction::Stop,
+            }),
+            health_check: Some(HealthCheck {
+                probe: HealthProbe::Tcp { port: 8080 },
+                interval_secs: 10,
+                restart_after: None,
+            }),
+            ..Default::default()
+        };
+        assert!(validate_elevated(&elevated).is_ok());
+
+        let restarted_by_limits = Executable {
+            limits: elevated.limits.clone().map(|limits| ResourceLimits {
+                action: LimitAction::Restart,
+                ..limits
+            }),
+            ..elevated.clone()
+        };
+        let restarted_by_health_check = Executable {
+            health_check: elevated.health_check.clone().map(|check| HealthCheck {
+                restart_after: Some(3),
+                ..check
+            }),
+            ..elevated.clone()
+        };
+        let started_on_focus = Executable {
+            focus_policy: Some(FocusPolicy::StartOnFocusGain),
+            ..elevated.clone()
+        };
+        let relaunched_on_focus = Executable {
+            focus_policy: Some(FocusPolicy::StopAndRestart),
+            ..elevated.clone()
+        };
+        let scheduled = Executable {
+            schedule: Some(Schedule::Every { minutes: 30 }),
+            ..elevated.clone()
+        };
+        let mut map_triggered = elevated.clone();
+        map_triggered
+            .map_triggers
+            .conditions
+            .push(TriggerCondition::GameMode {
+                mode: GameMode::Wvw,
+            });
+        let launched_on_transition = Executable {
+            launch_on: Some(GameEvent::InGameEntered),
+            ..elevated.clone()
+        };
+        let with_env = Executable {
+            env: BTreeMap::from([("KEY".to_string(), "value".to_string())]),
+            ..elevated
+        };
+        for executable in [
+            restarted_by_limits,
+            restarted_by_health_check,
+            started_on_focus,
+            relaunched_on_focus,
+            scheduled,
+            map_triggered,
+            launched_on_transition,
+            with_env,
+        ] {
+            assert!(validate_elevated(&executable).is_err());
+        }
+    }
+
+    #[test]
+    fn moved_executables_keep_their_order_after_reload() {
+        let dir = temp_addon_dir("move");
+        let mut manager = manager_with_links(&dir, &["a", "b", "c"]);
+
+        manager.move_exe(0, 2).unwrap();
+        assert_eq!(order(&manager), ["b", "c", "a"]);
+        assert!(manager.move_exe(0, 3).is_err());
+        assert!(manager.move_exe(3, 0).is_err());
+
+        let reloaded = ExeManager::new(dir.clone()).unwrap();
+        assert_eq!(order(&reloaded), ["b", "c", "a"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
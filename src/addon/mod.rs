@@ -6,15 +6,57 @@ Use the provided `Result<T>` type alias for fallible operations.
 
 ## Modules
 
+- [launch]: Launch strategies for programs, scripts and shortcuts
+- [limits]: Soft CPU and memory limits and the tracking of their breaches
 - [manager]: Executable management logic
+- [monitor]: Background thread sampling the CPU and memory usage of the running executables enforcing their limits and running their health and readiness checks
 - [ui]: UI rendering components
 - [init]: Initialization and cleanup routines
+- [keybinds]: Per-executable toggle keybinds registered with Nexus
+- [health]: Health probes (TCP port, HTTP 200, updated file) of the running executables
+- [readiness]: Readiness probes (window shown, port open, log line) telling when a launched executable is ready
+- [identity]: Automatic profile selection from the logged in character and account
+- [triggers]: Pure evaluation of map and game mode launch triggers
+- [diagnostics]: Wine/Proton environment diagnostics
+- [events]: Nexus event API letting other addons control the runner
+- [event_payload]: `#[repr(C)]` payloads of the event API
+- [focus]: Focus policies applied when the game window loses or regains focus
+- [game_state]: Pure detection of character select, loading screen and in-game transitions
+- [process]: Process control not covered by the standard library (job objects, suspend/resume, elevated launches, priority, affinity, usage, shell open)
+- [schedule]: Interval and time of day schedules with an injectable clock
+- [shell_link]: Parser of `.lnk` shortcut files
+- [status_block]: Status of the executables shared with other addons through the data link
+- [wine]: Wine detection and the bridge launching native Linux programs
+- [wine_path]: Pure translation between Unix paths and wine drive paths
+- [usage]: Pure CPU and memory usage history and formatting
+- [tick]: Per-frame update applying the triggers from MumbleLink and the schedules
 
 */
 
+pub mod diagnostics;
+pub mod event_payload;
+pub mod events;
+pub mod focus;
+pub mod game_state;
+pub mod health;
+pub mod identity;
 pub mod init;
+pub mod keybinds;
+pub mod launch;
+pub mod limits;
 pub mod manager;
+pub mod monitor;
+pub mod process;
+pub mod readiness;
+pub mod schedule;
+pub mod shell_link;
+pub mod status_block;
+pub mod tick;
+pub mod triggers;
 pub mod ui;
+pub mod usage;
+pub mod wine;
+pub mod wine_path;
 
 pub use init::{load, unload};
 
@@ -24,6 +66,8 @@ pub enum NexusError {
     ManagerInitialization(String),
     ProcessLaunch(String),
     ProcessStop(String),
+    /// The UAC prompt of an elevated launch was declined, holds the path of the executable
+    ElevationCancelled(String),
     FileOperation(String),
     ResourceLoading(String),
 }
@@ -36,6 +80,7 @@ impl std::fmt::Display for NexusError {
             }
             NexusError::ProcessLaunch(msg) => write!(f, "Process launch error: {msg}"),
             NexusError::ProcessStop(msg) => write!(f, "Process stop error: {msg}"),
+            NexusError::ElevationCancelled(path) => write!(f, "Elevation cancelled: {path}"),
             NexusError::FileOperation(msg) => write!(f, "File operation error: {msg}"),
             NexusError::ResourceLoading(msg) => write!(f, "Resource loading error: {msg}"),
         }
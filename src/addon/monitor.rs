@@ -0,0 +1,304 @@
+/*!
+
+Background thread sampling the CPU and memory usage of the running executables.
+
+Once per [SAMPLE_INTERVAL] the thread copies the pids out of the manager, releasing its lock
+before reading the counters through [process::usage] so the render thread is never blocked
+by the sampling. The counters of the processes an executable started, like the programs
+launched by a script, are added to its own. Histories of processes that are no longer running
+are dropped.
+
+Every sample is also checked against the [resource limits](crate::addon::limits) of the
+executable, the [health checks](crate::addon::health) that are due are run, and so are the
+[readiness probes](crate::addon::readiness) of the executables still starting. Results are
+reported through [ExeManager::enforce_limit], [ExeManager::record_health] and
+[ExeManager::record_readiness] once the usage lock is released, the two locks are never held
+together.
+
+*/
+
+use std::{
+    collections::HashMap,
+    sync::{
+        Mutex, MutexGuard,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::addon::{
+    health::{HealthCheck, HealthProbe},
+    limits::{LimitTracker, ResourceLimits},
+    manager::{EXE_MANAGER, ExeManager},
+    process,
+    readiness::{self, LogWatcher, ReadinessProbe},
+    usage::{RawUsage, SAMPLE_INTERVAL, Sample, UsageHistory},
+};
+
+/// Usage of a running executable
+struct Monitored {
+    /// Process the history belongs to, a restarted executable starts over
+    pid: u32,
+    history: UsageHistory,
+    limits: LimitTracker,
+    /// Last health probe, or when monitoring started
+    probed_at: Instant,
+    /// Lines appended to the log file of a log line readiness probe
+    log_watcher: Option<LogWatcher>,
+}
+
+impl Monitored {
+    fn new(pid: u32, now: Instant) -> Self {
+        Self {
+            pid,
+            history: UsageHistory::default(),
+            limits: LimitTracker::default(),
+            probed_at: now,
+            log_watcher: None,
+        }
+    }
+}
+
+/// Monitored running executables, by path
+static MONITORED: Mutex<Option<HashMap<String, Monitored>>> = Mutex::new(None);
+
+/// Running executable as copied out of the manager
+struct Running {
+    path: String,
+    pid: u32,
+    /// Process ids of the executable and of the processes it started
+    pids: Vec<u32>,
+    limits: Option<ResourceLimits>,
+    health_check: Option<HealthCheck>,
+    /// Suspended executables are not held to their limits nor health checked
+    suspended: bool,
+    /// Readiness probe and log offset at launch, while the executable is starting
+    starting: Option<(ReadinessProbe, u64)>,
+}
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+static THREAD: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+
+/// Starts the sampling thread
+pub fn start() {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let handle = thread::Builder::new()
+        .name("exe-runner-monitor".to_string())
+        .spawn(run);
+    match handle {
+        Ok(handle) => {
+            if let Ok(mut thread) = THREAD.lock() {
+                *thread = Some(handle);
+            }
+        }
+        Err(e) => {
+            RUNNING.store(false, Ordering::SeqCst);
+            log::error!("Failed to start the usage monitor: {e}");
+        }
+    }
+}
+
+/// Stops the sampling thread and waits for it to finish
+pub fn stop() {
+    RUNNING.store(false, Ordering::SeqCst);
+    let handle = THREAD.lock().ok().and_then(|mut thread| thread.take());
+    if let Some(handle) = handle {
+        handle.thread().unpark();
+        let _ = handle.join();
+    }
+}
+
+/// Returns the latest sample and the CPU percentages of the last minute of an executable
+pub fn usage(path: &str) -> Option<(Sample, Vec<f32>)> {
+    let monitored = MONITORED.lock().ok()?;
+    let history = &monitored.as_ref()?.get(path)?.history;
+    Some((history.latest()?, history.cpu_series()))
+}
+
+fn run() {
+    let core_count = process::core_count();
+    while RUNNING.load(Ordering::SeqCst) {
+        sample(core_count);
+        thread::park_timeout(SAMPLE_INTERVAL);
+    }
+    if let Ok(mut monitored) = MONITORED.lock() {
+        *monitored = None;
+    }
+}
+
+/// Samples every running executable once, enforces their limits and runs their health checks
+fn sample(core_count: usize) {
+    let Some(running) = running_executables() else {
+        return;
+    };
+
+    // Probes don't depend on the usage, they run even if it can't be read
+    let now = Instant::now();
+    let readings: Vec<_> = running
+        .into_iter()
+        .map(|running| {
+            let usage = total_usage(&running.pids)
+                .inspect_err(|e| log::debug!("Failed to read the usage of {}: {e}", running.path))
+                .ok();
+            (running, usage)
+        })
+        .collect();
+
+    let mut breaches = Vec::new();
+    let mut due_probes: Vec<(String, HealthProbe)> = Vec::new();
+    let mut starting = Vec::new();
+    {
+        let Ok(mut monitored) = MONITORED.lock() else {
+            return;
+        };
+        let monitored = monitored.get_or_insert_with(HashMap::new);
+        monitored.retain(|path, entry| {
+            readings
+                .iter()
+                .any(|(running, _)| &running.path == path && running.pid == entry.pid)
+        });
+        for (running, usage) in readings {
+            let entry = monitored
+                .entry(running.path.clone())
+                .or_insert_with(|| Monitored::new(running.pid, now));
+
+            if let Some((probe, log_offset)) = running.starting.clone() {
+                let log_watcher = entry.log_watcher.take().or_else(|| match &probe {
+                    ReadinessProbe::LogLine { pattern, .. } => {
+                        LogWatcher::new(pattern, log_offset).ok()
+                    }
+                    _ => None,
+                });
+                starting.push((
+                    running.path.clone(),
+                    running.pids.clone(),
+                    probe,
+                    log_watcher,
+                ));
+            }
+
+            if running.suspended {
+                // The first probe after resuming waits a whole interval
+                entry.probed_at = now;
+            } else if let Some(check) = &running.health_check {
+                let interval = Duration::from_secs(check.interval_secs);
+                if now.saturating_duration_since(entry.probed_at) >= interval {
+                    entry.probed_at = now;
+                    due_probes.push((running.path.clone(), check.probe.clone()));
+                }
+            }
+
+            let Some(sample) = usage.and_then(|usage| entry.history.record(now, usage, core_count))
+            else {
+                continue;
+            };
+            if running.suspended {
+                entry.limits = LimitTracker::default();
+                continue;
+            }
+            if let Some(limits) = &running.limits {
+                if let Some(breach) = entry.limits.update(limits, sample, now) {
+                    breaches.push((running.path, breach));
+                }
+            }
+        }
+    }
+
+    // Probes may block, they run without holding any lock
+    let probe_results: Vec<_> = due_probes
+        .into_iter()
+        .map(|(path, probe)| (path, probe.run()))
+        .collect();
+    let mut readiness_results = Vec::new();
+    let mut log_watchers = Vec::new();
+    for (path, pids, probe, mut log_watcher) in starting {
+        let ready = readiness::is_ready(&probe, &pids, log_watcher.as_mut());
+        readiness_results.push((path.clone(), ready));
+        if let Some(log_watcher) = log_watcher.filter(|_| !ready) {
+            log_watchers.push((path, log_watcher));
+        }
+    }
+    // Watchers keep their position in the log until the executable is ready
+    if let Ok(mut monitored) = MONITORED.lock() {
+        for (path, log_watcher) in log_watchers {
+            if let Some(entry) = monitored
+                .as_mut()
+                .and_then(|monitored| monitored.get_mut(&path))
+            {
+                entry.log_watcher = Some(log_watcher);
+            }
+        }
+    }
+
+    if breaches.is_empty() && probe_results.is_empty() && readiness_results.is_empty() {
+        return;
+    }
+    let Some(mut exe_manager) = lock_manager() else {
+        return;
+    };
+    for (path, breach) in breaches {
+        if let Err(e) = exe_manager.enforce_limit(&path, &breach) {
+            log::error!("Failed to enforce the resource limit of {path}: {e}");
+        }
+    }
+    for (path, ready) in readiness_results {
+        exe_manager.record_readiness(&path, ready);
+    }
+    for (path, result) in probe_results {
+        if let Err(e) = exe_manager.record_health(&path, result) {
+            log::error!("Failed to restart unhealthy executable {path}: {e}");
+        }
+    }
+}
+
+/// Copies the running executables out of the manager
+fn running_executables() -> Option<Vec<Running>> {
+    let exe_manager = lock_manager()?;
+    let running = exe_manager
+        .executables()
+        .iter()
+        .filter_map(|exe| {
+            Some(Running {
+                path: exe.path.clone(),
+                pid: exe_manager.pid(&exe.path)?,
+                pids: exe_manager.pids(&exe.path),
+                limits: exe.limits.clone(),
+                health_check: exe.health_check.clone(),
+                suspended: exe_manager.is_suspended(&exe.path),
+                starting: exe_manager.starting(&exe.path).and_then(|tracker| {
+                    let readiness = exe.readiness.as_ref()?;
+                    Some((readiness.probe.clone(), tracker.log_offset))
+                }),
+            })
+        })
+        .collect();
+    Some(running)
+}
+
+/// Adds up the counters of processes, skipping the ones that exited since they were listed
+fn total_usage(pids: &[u32]) -> std::io::Result<RawUsage> {
+    let mut total = None;
+    let mut error = None;
+    for &pid in pids {
+        match process::usage(pid) {
+            Ok(usage) => {
+                let total = total.get_or_insert(RawUsage {
+                    cpu_time: Duration::ZERO,
+                    working_set: 0,
+                });
+                total.cpu_time += usage.cpu_time;
+                total.working_set += usage.working_set;
+            }
+            Err(e) => error = Some(e),
+        }
+    }
+    total.ok_or_else(|| error.unwrap_or_else(|| std::io::Error::other("No process to read")))
+}
+
+fn lock_manager() -> Option<MutexGuard<'static, ExeManager>> {
+    EXE_MANAGER.get()?.lock().ok()
+}
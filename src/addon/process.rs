@@ -0,0 +1,844 @@
+/*!
+
+Process control operations that `std::process::Child` does not provide.
+
+Only the Windows API is used: the addon always runs inside the game, natively or under wine.
+Native Unix processes, like the Linux programs started through wine's `start.exe /unix`, are
+not supported. They are never suspended, stopped, measured or given a priority, and the
+settings that would need it are refused for them, see
+[ExeKind::tracks_program](crate::addon::manager::ExeKind::tracks_program).
+
+Spawned programs are started suspended and assigned to a job object before they run, so the
+processes they start belong to the same job: the `cmd` or `powershell` interpreter of a script
+and the programs it launches are stopped, suspended and measured together. The job kills what
+is left of them when its handle is closed, and an executable runs until its job is empty.
+
+Suspending uses `NtSuspendProcess`/`NtResumeProcess`, which wine also implements.
+
+Opening URLs uses the shell `open` verb, which wine forwards to the host browser through
+`winebrowser`.
+
+Priority classes and CPU affinity masks use `SetPriorityClass`/`SetProcessAffinityMask`, which
+wine maps to the nice value and affinity of the host process.
+
+Visible windows are found with `EnumWindows`, wine windows included. Native Linux windows
+are not visible from the Windows side.
+
+CPU time and working set are read with `GetProcessTimes`/`K32GetProcessMemoryInfo`. They are
+read by pid so the monitor thread doesn't need the `Child`, see [RunningProcess::pids].
+
+Elevated launches use `ShellExecuteExW` with the `runas` verb, which shows the UAC prompt and
+returns a handle to the new process instead of a `Child`. Both are tracked as a
+[RunningProcess] so they can be stopped, waited for and controlled the same way. Elevated
+processes can't be assigned to a job of the runner, only the started process is tracked.
+Declining the prompt fails with `ERROR_CANCELLED`, see [is_elevation_cancelled].
+
+*/
+
+use std::{
+    io,
+    process::{Child, Command, ExitStatus},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::addon::usage::RawUsage;
+
+/// Scheduling priority of a launched process, at most the game's own
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    Idle,
+    BelowNormal,
+    #[default]
+    Normal,
+    AboveNormal,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [
+        Priority::Idle,
+        Priority::BelowNormal,
+        Priority::Normal,
+        Priority::AboveNormal,
+    ];
+
+    pub fn is_normal(&self) -> bool {
+        *self == Priority::Normal
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::Idle => "Idle",
+            Priority::BelowNormal => "Below normal",
+            Priority::Normal => "Normal",
+            Priority::AboveNormal => "Above normal",
+        }
+    }
+}
+
+/// Process launched by the runner
+#[derive(Debug)]
+pub enum RunningProcess {
+    /// Spawned with `std::process::Command`, with the job holding it and its children
+    Spawned(Child, Option<imp::Job>),
+    /// Launched through the UAC prompt
+    Elevated(imp::ElevatedProcess),
+}
+
+impl RunningProcess {
+    /// Id of the started process, the interpreter of a script
+    pub fn id(&self) -> u32 {
+        match self {
+            RunningProcess::Spawned(child, _) => child.id(),
+            RunningProcess::Elevated(process) => process.id(),
+        }
+    }
+
+    /// Ids of the started process and of the processes it started that are still running
+    pub fn pids(&self) -> Vec<u32> {
+        let job_pids = match self {
+            RunningProcess::Spawned(_, Some(job)) => job
+                .pids()
+                .inspect_err(|e| log::debug!("Failed to list the processes of a job: {e}"))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        if job_pids.is_empty() {
+            vec![self.id()]
+        } else {
+            job_pids
+        }
+    }
+
+    /// Forces the process and the ones it started to exit, succeeding if they already did
+    pub fn kill(&mut self) -> io::Result<()> {
+        match self {
+            RunningProcess::Spawned(child, Some(job)) => job.terminate().or_else(|e| {
+                log::debug!("Failed to terminate a job, killing its process: {e}");
+                child.kill()
+            }),
+            RunningProcess::Spawned(child, None) => child.kill(),
+            RunningProcess::Elevated(process) => process.kill(),
+        }
+    }
+
+    /// Waits for the started process to exit
+    pub fn wait(&mut self) -> io::Result<ExitStatus> {
+        match self {
+            RunningProcess::Spawned(child, _) => child.wait(),
+            RunningProcess::Elevated(process) => process.wait(),
+        }
+    }
+
+    /**
+     * Returns the exit status of the started process once it and the processes it started
+     * exited, without blocking.
+     */
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        match self {
+            RunningProcess::Spawned(child, job) => {
+                let Some(status) = child.try_wait()? else {
+                    return Ok(None);
+                };
+                let has_children = job
+                    .as_ref()
+                    .is_some_and(|job| job.pids().is_ok_and(|pids| !pids.is_empty()));
+                Ok((!has_children).then_some(status))
+            }
+            RunningProcess::Elevated(process) => process.try_wait(),
+        }
+    }
+}
+
+impl From<Child> for RunningProcess {
+    fn from(child: Child) -> Self {
+        RunningProcess::Spawned(child, None)
+    }
+}
+
+/**
+ * Spawns a command in a new job and lets it run once `prepare` was called, so settings like
+ * the priority apply before the program runs its first instruction. The processes it starts
+ * join the job. If the job can't be set up, the process still runs but only it is tracked.
+ *
+ * # Arguments
+ * * `command` - Command created with the `CREATE_SUSPENDED` flag, so it can't start other
+ *   processes before it is in the job
+ * * `prepare` - Called with the suspended process
+ *
+ * # Errors
+ * Returns an error if spawning or resuming the process fails, the process is killed in the
+ * latter case.
+ */
+pub fn spawn_in_job(
+    command: &mut Command,
+    prepare: impl FnOnce(&RunningProcess),
+) -> io::Result<RunningProcess> {
+    let child = command.spawn()?;
+    let job = imp::Job::new()
+        .and_then(|job| job.assign(&child).map(|()| job))
+        .inspect_err(|e| log::warn!("Failed to put process {} in a job: {e}", child.id()))
+        .ok();
+    let mut process = RunningProcess::Spawned(child, job);
+    prepare(&process);
+    if let Err(e) = imp::resume_thread(&process) {
+        let _ = process.kill();
+        return Err(e);
+    }
+    Ok(process)
+}
+
+/**
+ * Launches a program with administrator rights, showing the UAC prompt.
+ * Blocks until the prompt is answered.
+ *
+ * # Arguments
+ * * `program` - Path of the program
+ * * `parameters` - Command line passed to the program
+ * * `working_dir` - Working directory of the program, if any
+ *
+ * # Errors
+ * Returns an error if the prompt was declined, see [is_elevation_cancelled], or the program
+ * could not be started.
+ */
+pub fn run_elevated(
+    program: &str,
+    parameters: &str,
+    working_dir: Option<&str>,
+) -> io::Result<RunningProcess> {
+    imp::run_elevated(program, parameters, working_dir).map(RunningProcess::Elevated)
+}
+
+/// Checks whether an elevated launch failed because the UAC prompt was declined
+pub fn is_elevation_cancelled(error: &io::Error) -> bool {
+    const ERROR_CANCELLED: i32 = 1223;
+    error.raw_os_error() == Some(ERROR_CANCELLED)
+}
+
+/// Suspends every thread of a process and of the processes it started
+pub fn suspend(process: &RunningProcess) -> io::Result<()> {
+    imp::suspend(process)
+}
+
+/// Resumes the processes suspended with [suspend]
+pub fn resume(process: &RunningProcess) -> io::Result<()> {
+    imp::resume(process)
+}
+
+/// Opens a URL or protocol link with its default handler, without tracking a process
+pub fn shell_open(target: &str) -> io::Result<()> {
+    imp::shell_open(target)
+}
+
+/// Sets the scheduling priority of a process
+pub fn set_priority(process: &RunningProcess, priority: Priority) -> io::Result<()> {
+    imp::set_priority(process, priority)
+}
+
+/// Restricts a process to the cores whose bit is set in `mask`
+pub fn set_affinity(process: &RunningProcess, mask: u64) -> io::Result<()> {
+    imp::set_affinity(process, mask)
+}
+
+/// Reads the cumulated CPU time and the working set of a process
+pub fn usage(pid: u32) -> io::Result<RawUsage> {
+    imp::usage(pid)
+}
+
+/// Checks whether a visible top level window belongs to a process
+pub fn has_visible_window(pid: u32) -> bool {
+    imp::has_visible_window(pid)
+}
+
+/// Number of logical cores the game can run on, 1 if it can't be determined
+pub fn core_count() -> usize {
+    std::thread::available_parallelism().map_or(1, |count| count.get())
+}
+
+/// Mask with a bit set for each of the first `core_count` cores
+pub fn all_cores_mask(core_count: usize) -> u64 {
+    if core_count >= 64 {
+        u64::MAX
+    } else {
+        (1 << core_count) - 1
+    }
+}
+
+/**
+ * Checks an affinity mask against the number of cores, `0` meaning no restriction.
+ *
+ * # Errors
+ * Returns an error naming the first core that does not exist.
+ */
+pub fn validate_affinity(mask: u64, core_count: usize) -> Result<(), String> {
+    let unknown = mask & !all_cores_mask(core_count);
+    if unknown == 0 {
+        Ok(())
+    } else {
+        Err(format!(
+            "CPU affinity uses core {} but this machine has {core_count} cores",
+            unknown.trailing_zeros()
+        ))
+    }
+}
+
+mod imp {
+    use std::{
+        ffi::c_void,
+        io,
+        iter::once,
+        os::windows::{
+            ffi::OsStrExt,
+            io::{AsRawHandle, FromRawHandle, OwnedHandle, RawHandle},
+            process::ExitStatusExt,
+        },
+        process::{Child, ExitStatus},
+    };
+
+    use super::{Priority, RawUsage, RunningProcess};
+
+    #[link(name = "ntdll")]
+    unsafe extern "system" {
+        fn NtSuspendProcess(process: *mut c_void) -> i32;
+        fn NtResumeProcess(process: *mut c_void) -> i32;
+    }
+
+    /// JOBOBJECT_BASIC_LIMIT_INFORMATION
+    #[repr(C)]
+    #[derive(Default)]
+    struct BasicLimitInformation {
+        per_process_user_time_limit: i64,
+        per_job_user_time_limit: i64,
+        limit_flags: u32,
+        minimum_working_set_size: usize,
+        maximum_working_set_size: usize,
+        active_process_limit: u32,
+        affinity: usize,
+        priority_class: u32,
+        scheduling_class: u32,
+    }
+
+    /// JOBOBJECT_EXTENDED_LIMIT_INFORMATION
+    #[repr(C)]
+    #[derive(Default)]
+    struct ExtendedLimitInformation {
+        basic: BasicLimitInformation,
+        /// IO_COUNTERS
+        io_info: [u64; 6],
+        process_memory_limit: usize,
+        job_memory_limit: usize,
+        peak_process_memory_used: usize,
+        peak_job_memory_used: usize,
+    }
+
+    /// JOBOBJECT_BASIC_PROCESS_ID_LIST, with room for the ids of 64 processes
+    #[repr(C)]
+    struct ProcessIdList {
+        assigned: u32,
+        listed: u32,
+        ids: [usize; 64],
+    }
+
+    const JOB_OBJECT_BASIC_PROCESS_ID_LIST: u32 = 3;
+    const JOB_OBJECT_EXTENDED_LIMIT_INFORMATION: u32 = 9;
+    const JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE: u32 = 0x2000;
+    /// A list too small for every id still holds the first ones
+    const ERROR_MORE_DATA: i32 = 234;
+    const PROCESS_SUSPEND_RESUME: u32 = 0x0800;
+
+    /// Job object killing its processes when the handle is closed on drop
+    #[derive(Debug)]
+    pub struct Job {
+        handle: OwnedHandle,
+    }
+
+    impl Job {
+        pub fn new() -> io::Result<Job> {
+            // SAFETY: both arguments may be null
+            let raw = unsafe { CreateJobObjectW(std::ptr::null_mut(), std::ptr::null()) };
+            if raw.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            // SAFETY: the handle was just created and is owned from now on
+            let job = Job {
+                handle: unsafe { OwnedHandle::from_raw_handle(raw) },
+            };
+
+            let info = ExtendedLimitInformation {
+                basic: BasicLimitInformation {
+                    limit_flags: JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            // SAFETY: the structure has the layout and size the information class expects
+            win32_result(unsafe {
+                SetInformationJobObject(
+                    job.raw(),
+                    JOB_OBJECT_EXTENDED_LIMIT_INFORMATION,
+                    &info as *const ExtendedLimitInformation as *const c_void,
+                    size_of::<ExtendedLimitInformation>() as u32,
+                )
+            })?;
+            Ok(job)
+        }
+
+        pub fn assign(&self, child: &Child) -> io::Result<()> {
+            // SAFETY: both handles are owned and stay valid for the duration of the call
+            win32_result(unsafe { AssignProcessToJobObject(self.raw(), child.as_raw_handle()) })
+        }
+
+        /// Ids of the processes of the job still running
+        pub fn pids(&self) -> io::Result<Vec<u32>> {
+            let mut list = ProcessIdList {
+                assigned: 0,
+                listed: 0,
+                ids: [0; 64],
+            };
+            // SAFETY: the structure has the layout the information class expects
+            win32_result(unsafe {
+                QueryInformationJobObject(
+                    self.raw(),
+                    JOB_OBJECT_BASIC_PROCESS_ID_LIST,
+                    &mut list as *mut ProcessIdList as *mut c_void,
+                    size_of::<ProcessIdList>() as u32,
+                    std::ptr::null_mut(),
+                )
+            })
+            .or_else(|e| match e.raw_os_error() {
+                Some(ERROR_MORE_DATA) => Ok(()),
+                _ => Err(e),
+            })?;
+            let listed = (list.listed as usize).min(list.ids.len());
+            Ok(list.ids[..listed].iter().map(|&id| id as u32).collect())
+        }
+
+        pub fn terminate(&self) -> io::Result<()> {
+            // SAFETY: the handle is owned by `self` and stays valid for the duration of the call
+            win32_result(unsafe { TerminateJobObject(self.raw(), 1) })
+        }
+
+        fn raw(&self) -> *mut c_void {
+            self.handle.as_raw_handle()
+        }
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn SetPriorityClass(process: *mut c_void, priority_class: u32) -> i32;
+        fn SetProcessAffinityMask(process: *mut c_void, affinity_mask: usize) -> i32;
+        fn OpenProcess(desired_access: u32, inherit_handle: i32, process_id: u32) -> *mut c_void;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+        fn GetProcessId(process: *mut c_void) -> u32;
+        fn TerminateProcess(process: *mut c_void, exit_code: u32) -> i32;
+        fn WaitForSingleObject(handle: *mut c_void, milliseconds: u32) -> u32;
+        fn GetExitCodeProcess(process: *mut c_void, exit_code: *mut u32) -> i32;
+        fn GetProcessTimes(
+            process: *mut c_void,
+            creation_time: *mut u64,
+            exit_time: *mut u64,
+            kernel_time: *mut u64,
+            user_time: *mut u64,
+        ) -> i32;
+        fn K32GetProcessMemoryInfo(
+            process: *mut c_void,
+            counters: *mut ProcessMemoryCounters,
+            size: u32,
+        ) -> i32;
+        fn CreateJobObjectW(attributes: *mut c_void, name: *const u16) -> *mut c_void;
+        fn SetInformationJobObject(
+            job: *mut c_void,
+            class: u32,
+            info: *const c_void,
+            length: u32,
+        ) -> i32;
+        fn AssignProcessToJobObject(job: *mut c_void, process: *mut c_void) -> i32;
+        fn QueryInformationJobObject(
+            job: *mut c_void,
+            class: u32,
+            info: *mut c_void,
+            length: u32,
+            return_length: *mut u32,
+        ) -> i32;
+        fn TerminateJobObject(job: *mut c_void, exit_code: u32) -> i32;
+    }
+
+    /// PROCESS_MEMORY_COUNTERS
+    #[repr(C)]
+    #[derive(Default)]
+    struct ProcessMemoryCounters {
+        cb: u32,
+        page_fault_count: u32,
+        peak_working_set_size: usize,
+        working_set_size: usize,
+        quota_peak_paged_pool_usage: usize,
+        quota_paged_pool_usage: usize,
+        quota_peak_non_paged_pool_usage: usize,
+        quota_non_paged_pool_usage: usize,
+        pagefile_usage: usize,
+        peak_pagefile_usage: usize,
+    }
+
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn EnumWindows(callback: EnumWindowsProc, lparam: isize) -> i32;
+        fn GetWindowThreadProcessId(window: *mut c_void, process_id: *mut u32) -> u32;
+        fn IsWindowVisible(window: *mut c_void) -> i32;
+    }
+
+    type EnumWindowsProc = unsafe extern "system" fn(*mut c_void, isize) -> i32;
+
+    #[link(name = "shell32")]
+    unsafe extern "system" {
+        fn ShellExecuteW(
+            hwnd: *mut c_void,
+            operation: *const u16,
+            file: *const u16,
+            parameters: *const u16,
+            directory: *const u16,
+            show_cmd: i32,
+        ) -> *mut c_void;
+        fn ShellExecuteExW(info: *mut ShellExecuteInfo) -> i32;
+    }
+
+    /// SHELLEXECUTEINFOW
+    #[repr(C)]
+    struct ShellExecuteInfo {
+        cb_size: u32,
+        mask: u32,
+        hwnd: *mut c_void,
+        verb: *const u16,
+        file: *const u16,
+        parameters: *const u16,
+        directory: *const u16,
+        show: i32,
+        inst_app: *mut c_void,
+        id_list: *mut c_void,
+        class: *const u16,
+        hkey_class: *mut c_void,
+        hot_key: u32,
+        icon_or_monitor: *mut c_void,
+        process: *mut c_void,
+    }
+
+    const SW_SHOWNORMAL: i32 = 1;
+    const SEE_MASK_NOCLOSEPROCESS: u32 = 0x0040;
+    const SEE_MASK_NOASYNC: u32 = 0x0100;
+    const WAIT_OBJECT_0: u32 = 0;
+    const WAIT_TIMEOUT: u32 = 0x0102;
+    const INFINITE: u32 = u32::MAX;
+
+    /// Process launched with `ShellExecuteExW`, the handle is closed on drop
+    #[derive(Debug)]
+    pub struct ElevatedProcess {
+        handle: OwnedHandle,
+        pid: u32,
+    }
+
+    impl ElevatedProcess {
+        pub fn id(&self) -> u32 {
+            self.pid
+        }
+
+        pub fn kill(&mut self) -> io::Result<()> {
+            // SAFETY: the handle is owned by `self` and stays valid for the duration of the call
+            let result = win32_result(unsafe { TerminateProcess(self.raw(), 1) });
+            // Terminating a process that already exited is denied, like `Child::kill` it succeeds
+            match result {
+                Err(_) if self.try_wait()?.is_some() => Ok(()),
+                result => result,
+            }
+        }
+
+        pub fn wait(&mut self) -> io::Result<ExitStatus> {
+            self.wait_for(INFINITE)?
+                .ok_or_else(|| io::Error::other("Process did not exit"))
+        }
+
+        pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+            self.wait_for(0)
+        }
+
+        fn wait_for(&self, milliseconds: u32) -> io::Result<Option<ExitStatus>> {
+            // SAFETY: the handle is owned by `self` and stays valid for the duration of the calls
+            match unsafe { WaitForSingleObject(self.raw(), milliseconds) } {
+                WAIT_OBJECT_0 => {
+                    let mut exit_code = 0;
+                    win32_result(unsafe { GetExitCodeProcess(self.raw(), &mut exit_code) })?;
+                    Ok(Some(ExitStatus::from_raw(exit_code)))
+                }
+                WAIT_TIMEOUT => Ok(None),
+                _ => Err(io::Error::last_os_error()),
+            }
+        }
+
+        fn raw(&self) -> *mut c_void {
+            self.handle.as_raw_handle()
+        }
+    }
+
+    impl AsRawHandle for RunningProcess {
+        fn as_raw_handle(&self) -> RawHandle {
+            match self {
+                RunningProcess::Spawned(child, _) => child.as_raw_handle(),
+                RunningProcess::Elevated(process) => process.raw(),
+            }
+        }
+    }
+
+    pub fn run_elevated(
+        program: &str,
+        parameters: &str,
+        working_dir: Option<&str>,
+    ) -> io::Result<ElevatedProcess> {
+        let verb = wide("runas");
+        let file = wide(program);
+        let parameters = wide(parameters);
+        let directory = working_dir.map(wide);
+        let mut info = ShellExecuteInfo {
+            cb_size: size_of::<ShellExecuteInfo>() as u32,
+            mask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NOASYNC,
+            hwnd: std::ptr::null_mut(),
+            verb: verb.as_ptr(),
+            file: file.as_ptr(),
+            parameters: parameters.as_ptr(),
+            directory: directory
+                .as_ref()
+                .map_or(std::ptr::null(), |directory| directory.as_ptr()),
+            show: SW_SHOWNORMAL,
+            inst_app: std::ptr::null_mut(),
+            id_list: std::ptr::null_mut(),
+            class: std::ptr::null(),
+            hkey_class: std::ptr::null_mut(),
+            hot_key: 0,
+            icon_or_monitor: std::ptr::null_mut(),
+            process: std::ptr::null_mut(),
+        };
+
+        // SAFETY: every string is nul terminated and outlives the call
+        win32_result(unsafe { ShellExecuteExW(&mut info) })?;
+        if info.process.is_null() {
+            return Err(io::Error::other(format!(
+                "No process was started for {program}"
+            )));
+        }
+        // SAFETY: SEE_MASK_NOCLOSEPROCESS hands the process handle over to the caller
+        let handle = unsafe { OwnedHandle::from_raw_handle(info.process) };
+        // SAFETY: the handle is valid, it was checked above
+        let pid = unsafe { GetProcessId(handle.as_raw_handle()) };
+        Ok(ElevatedProcess { handle, pid })
+    }
+
+    const IDLE_PRIORITY_CLASS: u32 = 0x0040;
+    const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x4000;
+    const NORMAL_PRIORITY_CLASS: u32 = 0x0020;
+    const ABOVE_NORMAL_PRIORITY_CLASS: u32 = 0x8000;
+
+    pub fn suspend(process: &RunningProcess) -> io::Result<()> {
+        for_each_process(process, |handle| {
+            // SAFETY: the handle stays valid for the duration of the call
+            nt_result(unsafe { NtSuspendProcess(handle) }, "NtSuspendProcess")
+        })
+    }
+
+    pub fn resume(process: &RunningProcess) -> io::Result<()> {
+        for_each_process(process, |handle| {
+            // SAFETY: the handle stays valid for the duration of the call
+            nt_result(unsafe { NtResumeProcess(handle) }, "NtResumeProcess")
+        })
+    }
+
+    /// Resumes the main thread of a process created suspended
+    pub fn resume_thread(process: &RunningProcess) -> io::Result<()> {
+        // SAFETY: the handle is owned by `process` and stays valid for the duration of the call
+        let status = unsafe { NtResumeProcess(process.as_raw_handle()) };
+        nt_result(status, "NtResumeProcess")
+    }
+
+    /**
+     * Calls `action` with a handle of every process of the job of a process, or of the process
+     * alone when it has no job. Processes that exited in between are skipped, the first other
+     * error is returned.
+     */
+    fn for_each_process(
+        process: &RunningProcess,
+        mut action: impl FnMut(*mut c_void) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let RunningProcess::Spawned(_, Some(job)) = process else {
+            return action(process.as_raw_handle());
+        };
+        for pid in job.pids()? {
+            // SAFETY: the handle is checked and closed below
+            let handle = unsafe { OpenProcess(PROCESS_SUSPEND_RESUME, 0, pid) };
+            if handle.is_null() {
+                log::debug!(
+                    "Failed to open process {pid}: {}",
+                    io::Error::last_os_error()
+                );
+                continue;
+            }
+            let result = action(handle);
+            // SAFETY: the handle was opened above and is not used afterwards
+            unsafe { CloseHandle(handle) };
+            result?;
+        }
+        Ok(())
+    }
+
+    pub fn shell_open(target: &str) -> io::Result<()> {
+        let operation = wide("open");
+        let file = wide(target);
+
+        // SAFETY: both strings are nul terminated and outlive the call
+        let result = unsafe {
+            ShellExecuteW(
+                std::ptr::null_mut(),
+                operation.as_ptr(),
+                file.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                SW_SHOWNORMAL,
+            )
+        };
+        // Values up to 32 are error codes
+        let code = result as usize;
+        if code <= 32 {
+            Err(io::Error::other(format!(
+                "ShellExecuteW failed with code {code}"
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn set_priority(process: &RunningProcess, priority: Priority) -> io::Result<()> {
+        let class = match priority {
+            Priority::Idle => IDLE_PRIORITY_CLASS,
+            Priority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            Priority::Normal => NORMAL_PRIORITY_CLASS,
+            Priority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+        };
+        // SAFETY: the handle is owned by `process` and stays valid for the duration of the call
+        win32_result(unsafe { SetPriorityClass(process.as_raw_handle(), class) })
+    }
+
+    pub fn set_affinity(process: &RunningProcess, mask: u64) -> io::Result<()> {
+        let mask = usize::try_from(mask).map_err(io::Error::other)?;
+        // SAFETY: the handle is owned by `process` and stays valid for the duration of the call
+        win32_result(unsafe { SetProcessAffinityMask(process.as_raw_handle(), mask) })
+    }
+
+    pub fn usage(pid: u32) -> io::Result<RawUsage> {
+        // SAFETY: the handle is checked and closed below
+        let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+        if process.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+
+        let (mut creation, mut exit, mut kernel, mut user) = (0, 0, 0, 0);
+        let mut counters = ProcessMemoryCounters {
+            cb: size_of::<ProcessMemoryCounters>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: every out pointer is valid for the duration of the calls
+        let result = unsafe {
+            win32_result(GetProcessTimes(
+                process,
+                &mut creation,
+                &mut exit,
+                &mut kernel,
+                &mut user,
+            ))
+            .and_then(|()| {
+                win32_result(K32GetProcessMemoryInfo(process, &mut counters, counters.cb))
+            })
+        };
+        // SAFETY: the handle was opened above and is not used afterwards
+        unsafe { CloseHandle(process) };
+
+        // FILETIME durations are in 100 ns units
+        result.map(|()| RawUsage {
+            cpu_time: std::time::Duration::from_nanos((kernel + user) * 100),
+            working_set: counters.working_set_size as u64,
+        })
+    }
+
+    pub fn has_visible_window(pid: u32) -> bool {
+        struct Search {
+            pid: u32,
+            found: bool,
+        }
+
+        unsafe extern "system" fn visit(window: *mut c_void, lparam: isize) -> i32 {
+            // SAFETY: lparam is the `Search` passed to EnumWindows below, alive during the call
+            let search = unsafe { &mut *(lparam as *mut Search) };
+            let mut window_pid = 0;
+            unsafe { GetWindowThreadProcessId(window, &mut window_pid) };
+            if window_pid == search.pid && unsafe { IsWindowVisible(window) } != 0 {
+                search.found = true;
+                // Stop enumerating
+                return 0;
+            }
+            1
+        }
+
+        let mut search = Search { pid, found: false };
+        // SAFETY: the callback only uses `search`, which outlives the enumeration
+        unsafe { EnumWindows(visit, &mut search as *mut Search as isize) };
+        search.found
+    }
+
+    /// Encodes a string as a nul terminated UTF-16 string
+    fn wide(value: &str) -> Vec<u16> {
+        std::ffi::OsStr::new(value)
+            .encode_wide()
+            .chain(once(0))
+            .collect()
+    }
+
+    /// Converts a Win32 BOOL into a `Result`, reading the last error on failure
+    fn win32_result(result: i32) -> io::Result<()> {
+        if result == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Converts an NTSTATUS into a `Result`, negative values being errors
+    fn nt_result(status: i32, function: &str) -> io::Result<()> {
+        if status < 0 {
+            Err(io::Error::other(format!(
+                "{function} failed with NTSTATUS {status:#010x}"
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mask_of_all_cores() {
+        assert_eq!(all_cores_mask(1), 0b1);
+        assert_eq!(all_cores_mask(8), 0xFF);
+        assert_eq!(all_cores_mask(64), u64::MAX);
+        assert_eq!(all_cores_mask(128), u64::MAX);
+    }
+
+    #[test]
+    fn affinity_is_validated_against_the_core_count() {
+        assert!(validate_affinity(0, 8).is_ok());
+        assert!(validate_affinity(0b1100_0000, 8).is_ok());
+        assert_eq!(
+            validate_affinity(0b1_0000_0001, 8),
+            Err("CPU affinity uses core 8 but this machine has 8 cores".to_string())
+        );
+    }
+}
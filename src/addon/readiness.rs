@@ -0,0 +1,332 @@
+/*!
+
+Readiness of a launched executable: a process is "Starting" from its spawn until it is ready
+to be used, then "Ready", or "Failed" if that did not happen within the timeout.
+
+A process is ready when one [ReadinessProbe] succeeds:
+- one of its windows, or of the processes it started, is visible
+- a localhost TCP port accepts connections
+- a line matching a regex is appended to a log file after the launch
+
+Executables without a readiness probe are ready as soon as they are spawned. The probes are
+run by the monitor thread every sample, the [ReadinessTracker] kept by the manager applies
+the timeout.
+
+*/
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    time::{Duration, Instant},
+};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::addon::{health::HealthProbe, process};
+
+/// What makes an executable ready
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReadinessProbe {
+    /// A visible window belongs to the process
+    Window,
+    /// A localhost TCP port accepts connections
+    Port { port: u16 },
+    /// A line matching `pattern` is written to the log file at `path`
+    LogLine { path: String, pattern: String },
+}
+
+/// Readiness settings of an executable
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Readiness {
+    pub probe: ReadinessProbe,
+    /// Time the executable has to become ready before it is marked failed
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl Readiness {
+    pub fn new(probe: ReadinessProbe) -> Self {
+        Self {
+            probe,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+
+    /**
+     * Checks that the readiness probe can be run.
+     *
+     * # Errors
+     * Returns an error describing the first invalid value.
+     */
+    pub fn validate(&self) -> Result<(), String> {
+        if self.timeout_secs == 0 {
+            return Err("Readiness timeout must be at least 1 second".into());
+        }
+        match &self.probe {
+            ReadinessProbe::Window => Ok(()),
+            ReadinessProbe::Port { port: 0 } => Err("Readiness port must not be 0".into()),
+            ReadinessProbe::Port { .. } => Ok(()),
+            ReadinessProbe::LogLine { path, .. } if path.trim().is_empty() => {
+                Err("Readiness log file must not be empty".into())
+            }
+            ReadinessProbe::LogLine { pattern, .. } => Regex::new(pattern)
+                .map(drop)
+                .map_err(|e| format!("Invalid readiness pattern: {e}")),
+        }
+    }
+}
+
+/// Startup state of a running executable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadyState {
+    Starting,
+    Ready,
+    /// Not ready within the timeout, the process keeps running
+    Failed,
+}
+
+impl ReadyState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReadyState::Starting => "Starting",
+            ReadyState::Ready => "Ready",
+            ReadyState::Failed => "Not ready",
+        }
+    }
+}
+
+/// Startup of one launched executable
+#[derive(Debug, Clone)]
+pub struct ReadinessTracker {
+    pub state: ReadyState,
+    pub started: Instant,
+    timeout: Duration,
+    /// Length of the log file at launch, earlier lines don't count
+    pub log_offset: u64,
+}
+
+impl ReadinessTracker {
+    /**
+     * Starts tracking a launch.
+     *
+     * # Arguments
+     * * `readiness` - Readiness settings of the executable
+     * * `now` - Launch time
+     */
+    pub fn new(readiness: &Readiness, now: Instant) -> Self {
+        let log_offset = match &readiness.probe {
+            ReadinessProbe::LogLine { path, .. } => std::fs::metadata(path).map_or(0, |m| m.len()),
+            _ => 0,
+        };
+        Self {
+            state: ReadyState::Starting,
+            started: now,
+            timeout: Duration::from_secs(readiness.timeout_secs),
+            log_offset,
+        }
+    }
+
+    /**
+     * Updates the state with the result of a probe.
+     *
+     * # Returns
+     * The new state if it changed.
+     */
+    pub fn update(&mut self, ready: bool, now: Instant) -> Option<ReadyState> {
+        if self.state != ReadyState::Starting {
+            return None;
+        }
+        let state = if ready {
+            ReadyState::Ready
+        } else if now.saturating_duration_since(self.started) >= self.timeout {
+            ReadyState::Failed
+        } else {
+            return None;
+        };
+        self.state = state;
+        Some(state)
+    }
+}
+
+/// Reads the lines appended to a log file, looking for a pattern
+#[derive(Debug)]
+pub struct LogWatcher {
+    pattern: Regex,
+    offset: u64,
+    /// Last line, not terminated yet
+    partial: String,
+}
+
+impl LogWatcher {
+    /**
+     * Creates a watcher reading from `offset`.
+     *
+     * # Errors
+     * Returns an error if the pattern is not a valid regex.
+     */
+    pub fn new(pattern: &str, offset: u64) -> Result<Self, String> {
+        Ok(Self {
+            pattern: Regex::new(pattern).map_err(|e| e.to_string())?,
+            offset,
+            partial: String::new(),
+        })
+    }
+
+    /**
+     * Reads what was appended since the last call.
+     * A file shorter than what was already read was truncated and is read again from the start.
+     *
+     * # Returns
+     * Whether a complete new line matches the pattern.
+     */
+    pub fn check(&mut self, path: &str) -> io::Result<bool> {
+        let mut file = File::open(path)?;
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut appended = Vec::new();
+        self.offset += file.read_to_end(&mut appended)? as u64;
+
+        self.partial.push_str(&String::from_utf8_lossy(&appended));
+        let Some(end) = self.partial.rfind('\n') else {
+            return Ok(false);
+        };
+        let matched = self.partial[..end]
+            .lines()
+            .any(|line| self.pattern.is_match(line));
+        self.partial.drain(..=end);
+        Ok(matched)
+    }
+}
+
+/**
+ * Runs a readiness probe once.
+ *
+ * # Arguments
+ * * `probe` - Probe of the executable
+ * * `pids` - Process ids of the executable and of the processes it started
+ * * `log_watcher` - Watcher of the log file, for log line probes
+ */
+pub fn is_ready(
+    probe: &ReadinessProbe,
+    pids: &[u32],
+    log_watcher: Option<&mut LogWatcher>,
+) -> bool {
+    match probe {
+        ReadinessProbe::Window => pids.iter().any(|&pid| process::has_visible_window(pid)),
+        ReadinessProbe::Port { port } => HealthProbe::Tcp { port: *port }.run().is_ok(),
+        ReadinessProbe::LogLine { path, .. } => log_watcher.is_some_and(|log_watcher| {
+            log_watcher
+                .check(path)
+                .inspect_err(|e| log::debug!("Failed to read {path}: {e}"))
+                .unwrap_or(false)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Write, net::TcpListener};
+
+    fn temp_log(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("exe-runner-{name}-{}.log", std::process::id()))
+    }
+
+    #[test]
+    fn ready_before_the_timeout() {
+        let start = Instant::now();
+        let readiness = Readiness::new(ReadinessProbe::Window);
+        let mut tracker = ReadinessTracker::new(&readiness, start);
+
+        assert_eq!(tracker.update(false, start + Duration::from_secs(5)), None);
+        assert_eq!(
+            tracker.update(true, start + Duration::from_secs(6)),
+            Some(ReadyState::Ready)
+        );
+        // Final states don't change anymore
+        assert_eq!(tracker.update(false, start + Duration::from_secs(60)), None);
+        assert_eq!(tracker.state, ReadyState::Ready);
+    }
+
+    #[test]
+    fn failed_after_the_timeout() {
+        let start = Instant::now();
+        let readiness = Readiness {
+            probe: ReadinessProbe::Port { port: 8080 },
+            timeout_secs: 10,
+        };
+        let mut tracker = ReadinessTracker::new(&readiness, start);
+
+        assert_eq!(tracker.update(false, start + Duration::from_secs(9)), None);
+        assert_eq!(
+            tracker.update(false, start + Duration::from_secs(10)),
+            Some(ReadyState::Failed)
+        );
+        assert_eq!(tracker.update(true, start + Duration::from_secs(11)), None);
+    }
+
+    #[test]
+    fn log_lines_written_before_the_launch_are_ignored() {
+        let path = temp_log("readiness");
+        std::fs::write(&path, "Listening on 8080\n").unwrap();
+        let path_text = path.to_string_lossy().into_owned();
+        let readiness = Readiness::new(ReadinessProbe::LogLine {
+            path: path_text.clone(),
+            pattern: r"Listening on \d+".into(),
+        });
+        let tracker = ReadinessTracker::new(&readiness, Instant::now());
+        let mut watcher = LogWatcher::new(r"Listening on \d+", tracker.log_offset).unwrap();
+
+        assert!(!watcher.check(&path_text).unwrap());
+
+        let mut file = File::options().append(true).open(&path).unwrap();
+        write!(file, "Loading\nListening on").unwrap();
+        assert!(!watcher.check(&path_text).unwrap());
+        writeln!(file, " 9000").unwrap();
+        assert!(watcher.check(&path_text).unwrap());
+
+        // Truncated and rewritten by a new run
+        std::fs::write(&path, "Listening on 1\n").unwrap();
+        assert!(watcher.check(&path_text).unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.check(&path_text).is_err());
+    }
+
+    #[test]
+    fn port_probe() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(is_ready(&ReadinessProbe::Port { port }, &[], None));
+        drop(listener);
+        assert!(!is_ready(&ReadinessProbe::Port { port }, &[], None));
+    }
+
+    #[test]
+    fn validation_and_serialization() {
+        let invalid_pattern = Readiness::new(ReadinessProbe::LogLine {
+            path: "C:\\tool\\log.txt".into(),
+            pattern: "(unclosed".into(),
+        });
+        assert!(invalid_pattern.validate().is_err());
+        assert!(
+            Readiness::new(ReadinessProbe::Port { port: 0 })
+                .validate()
+                .is_err()
+        );
+
+        let readiness: Readiness = serde_json::from_str(r#"{"probe":{"type":"window"}}"#).unwrap();
+        assert_eq!(readiness.probe, ReadinessProbe::Window);
+        assert_eq!(readiness.timeout_secs, 30);
+        assert!(readiness.validate().is_ok());
+    }
+}
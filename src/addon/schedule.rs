@@ -0,0 +1,257 @@
+/*!
+
+Scheduled launches, either on a fixed interval or at a local time of day.
+
+The scheduler takes its time from an injectable [Clock] so the computation of the next runs
+can be tested deterministically. Launching the due executables is done by the caller.
+
+*/
+
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Local, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// Source of the current local time
+pub trait Clock {
+    fn now(&self) -> NaiveDateTime;
+}
+
+/// Clock reading the system's local time
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+}
+
+/// When an executable should be launched
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Every `minutes` minutes, starting `minutes` after the schedule is set up
+    Every { minutes: u32 },
+    /// Every day at the given local time
+    DailyAt { hour: u32, minute: u32 },
+}
+
+impl Schedule {
+    /// Checks that the interval is positive and the time of day exists
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Schedule::Every { minutes: 0 } => Err("Interval must be at least one minute".into()),
+            Schedule::Every { .. } => Ok(()),
+            Schedule::DailyAt { hour, minute } => NaiveTime::from_hms_opt(*hour, *minute, 0)
+                .map(|_| ())
+                .ok_or_else(|| format!("Invalid time of day: {hour:02}:{minute:02}")),
+        }
+    }
+
+    /// Returns the first run strictly after `after`, `None` for an invalid schedule
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Schedule::Every { minutes: 0 } => None,
+            Schedule::Every { minutes } => Some(after + Duration::minutes(i64::from(*minutes))),
+            Schedule::DailyAt { hour, minute } => {
+                let time = NaiveTime::from_hms_opt(*hour, *minute, 0)?;
+                let today = after.date().and_time(time);
+                if today > after {
+                    Some(today)
+                } else {
+                    Some(today + Duration::days(1))
+                }
+            }
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Schedule::Every { minutes } => format!("Every {minutes} min"),
+            Schedule::DailyAt { hour, minute } => format!("Daily at {hour:02}:{minute:02}"),
+        }
+    }
+}
+
+/// Keeps track of the next run of every scheduled executable, keyed by path
+#[derive(Debug)]
+pub struct Scheduler<C: Clock> {
+    clock: C,
+    next_runs: BTreeMap<String, (Schedule, NaiveDateTime)>,
+}
+
+impl<C: Clock> Scheduler<C> {
+    pub const fn new(clock: C) -> Self {
+        Self {
+            clock,
+            next_runs: BTreeMap::new(),
+        }
+    }
+
+    /**
+     * Synchronizes the scheduler with the configured schedules.
+     * New or changed schedules get their next run computed from now,
+     * executables without a schedule are forgotten.
+     *
+     * # Arguments
+     * * `schedules` - Path and schedule of every executable
+     */
+    pub fn sync<'a>(
+        &mut self,
+        schedules: impl IntoIterator<Item = (&'a str, Option<&'a Schedule>)>,
+    ) {
+        let now = self.clock.now();
+        let mut next_runs = BTreeMap::new();
+
+        for (path, schedule) in schedules {
+            let Some(schedule) = schedule else {
+                continue;
+            };
+            let next_run = match self.next_runs.remove(path) {
+                Some((previous, next_run)) if &previous == schedule => Some(next_run),
+                _ => schedule.next_after(now),
+            };
+            if let Some(next_run) = next_run {
+                next_runs.insert(path.to_string(), (schedule.clone(), next_run));
+            }
+        }
+
+        self.next_runs = next_runs;
+    }
+
+    /**
+     * Returns the executables whose run is due and schedules their next run.
+     * Runs missed while the game was not running are not caught up: the next run
+     * is computed from now.
+     */
+    pub fn take_due(&mut self) -> Vec<String> {
+        let now = self.clock.now();
+        let mut due = Vec::new();
+
+        for (path, (schedule, next_run)) in &mut self.next_runs {
+            if *next_run <= now {
+                due.push(path.clone());
+                match schedule.next_after(now) {
+                    Some(next) => *next_run = next,
+                    None => *next_run = NaiveDateTime::MAX,
+                }
+            }
+        }
+
+        due
+    }
+
+    /// Returns the next run of an executable, if it is scheduled
+    pub fn next_run(&self, path: &str) -> Option<NaiveDateTime> {
+        self.next_runs.get(path).map(|(_, next_run)| *next_run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::{cell::Cell, rc::Rc};
+
+    use chrono::NaiveDate;
+
+    type FakeClock = Rc<Cell<NaiveDateTime>>;
+
+    impl Clock for FakeClock {
+        fn now(&self) -> NaiveDateTime {
+            self.get()
+        }
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 5, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn scheduler_at(now: NaiveDateTime) -> (FakeClock, Scheduler<FakeClock>) {
+        let clock = Rc::new(Cell::new(now));
+        (clock.clone(), Scheduler::new(clock))
+    }
+
+    #[test]
+    fn interval_runs_every_period() {
+        let schedule = Schedule::Every { minutes: 30 };
+        let (clock, mut scheduler) = scheduler_at(at(1, 10, 0));
+        scheduler.sync([("backup.exe", Some(&schedule))]);
+
+        assert_eq!(scheduler.next_run("backup.exe"), Some(at(1, 10, 30)));
+        assert!(scheduler.take_due().is_empty());
+
+        clock.set(at(1, 10, 30));
+        assert_eq!(scheduler.take_due(), vec!["backup.exe".to_string()]);
+        assert_eq!(scheduler.next_run("backup.exe"), Some(at(1, 11, 0)));
+        assert!(scheduler.take_due().is_empty());
+    }
+
+    #[test]
+    fn daily_runs_today_or_tomorrow() {
+        let schedule = Schedule::DailyAt {
+            hour: 20,
+            minute: 0,
+        };
+        assert_eq!(schedule.next_after(at(1, 19, 59)), Some(at(1, 20, 0)));
+        assert_eq!(schedule.next_after(at(1, 20, 0)), Some(at(2, 20, 0)));
+        assert_eq!(schedule.next_after(at(1, 23, 0)), Some(at(2, 20, 0)));
+    }
+
+    #[test]
+    fn missed_runs_are_not_caught_up() {
+        let schedule = Schedule::Every { minutes: 30 };
+        let (clock, mut scheduler) = scheduler_at(at(1, 10, 0));
+        scheduler.sync([("backup.exe", Some(&schedule))]);
+
+        clock.set(at(1, 13, 10));
+        assert_eq!(scheduler.take_due().len(), 1);
+        assert_eq!(scheduler.next_run("backup.exe"), Some(at(1, 13, 40)));
+        assert!(scheduler.take_due().is_empty());
+    }
+
+    #[test]
+    fn sync_keeps_unchanged_and_resets_changed_schedules() {
+        let every = Schedule::Every { minutes: 30 };
+        let daily = Schedule::DailyAt {
+            hour: 20,
+            minute: 0,
+        };
+        let (clock, mut scheduler) = scheduler_at(at(1, 10, 0));
+        scheduler.sync([("a.exe", Some(&every)), ("b.exe", Some(&every))]);
+
+        clock.set(at(1, 10, 10));
+        scheduler.sync([("a.exe", Some(&every)), ("b.exe", Some(&daily))]);
+        assert_eq!(scheduler.next_run("a.exe"), Some(at(1, 10, 30)));
+        assert_eq!(scheduler.next_run("b.exe"), Some(at(1, 20, 0)));
+
+        scheduler.sync([("a.exe", None), ("b.exe", Some(&daily))]);
+        assert_eq!(scheduler.next_run("a.exe"), None);
+    }
+
+    #[test]
+    fn validation() {
+        assert!(Schedule::Every { minutes: 0 }.validate().is_err());
+        assert!(Schedule::Every { minutes: 5 }.validate().is_ok());
+        assert!(
+            Schedule::DailyAt {
+                hour: 24,
+                minute: 0
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            Schedule::DailyAt {
+                hour: 23,
+                minute: 59
+            }
+            .validate()
+            .is_ok()
+        );
+    }
+}
@@ -0,0 +1,343 @@
+/*!
+
+Parser of the Shell Link binary format (`.lnk` shortcut files), as specified in
+[MS-SHLLINK](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-shllink/).
+
+Only what is needed to launch the shortcut is extracted: target path, arguments and working
+directory. Targets only described by their item id list (e.g. shell folders) are not resolved.
+Non Unicode strings are decoded as Latin-1.
+
+*/
+
+use std::ops::Range;
+
+/// Size of the ShellLinkHeader structure
+const HEADER_SIZE: usize = 0x4C;
+
+/// Class identifier every shell link starts with: 00021401-0000-0000-C000-000000000046
+const LINK_CLSID: [u8; 16] = [
+    0x01, 0x14, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46,
+];
+
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x01;
+const HAS_LINK_INFO: u32 = 0x02;
+const HAS_NAME: u32 = 0x04;
+const HAS_RELATIVE_PATH: u32 = 0x08;
+const HAS_WORKING_DIR: u32 = 0x10;
+const HAS_ARGUMENTS: u32 = 0x20;
+const IS_UNICODE: u32 = 0x80;
+const FORCE_NO_LINK_INFO: u32 = 0x100;
+
+const VOLUME_ID_AND_LOCAL_BASE_PATH: u32 = 0x01;
+
+/// What a shortcut launches
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShellLink {
+    /// Absolute target path, from the link info
+    pub local_path: Option<String>,
+    /// Target path relative to the shortcut file
+    pub relative_path: Option<String>,
+    pub working_dir: Option<String>,
+    pub arguments: Option<String>,
+}
+
+impl ShellLink {
+    /**
+     * Returns the path of the target, resolving the relative path against the
+     * directory of the shortcut when there is no absolute path.
+     *
+     * # Arguments
+     * * `link_dir` - Directory containing the shortcut file
+     */
+    pub fn target(&self, link_dir: &str) -> Option<String> {
+        if let Some(local_path) = &self.local_path {
+            return Some(local_path.clone());
+        }
+        let relative_path = self.relative_path.as_ref()?;
+        let relative_path = relative_path.trim_start_matches(".\\");
+        Some(format!(
+            "{}\\{relative_path}",
+            link_dir.trim_end_matches(['\\', '/'])
+        ))
+    }
+}
+
+/**
+ * Parses a shell link file.
+ *
+ * # Errors
+ * Returns an error if the data is not a shell link or is truncated.
+ */
+pub fn parse(data: &[u8]) -> Result<ShellLink, String> {
+    let mut reader = Reader { data, position: 0 };
+
+    if reader.u32()? as usize != HEADER_SIZE || reader.bytes(16)? != LINK_CLSID {
+        return Err("Not a shell link file".into());
+    }
+    let flags = reader.u32()?;
+    reader.seek(HEADER_SIZE)?;
+
+    if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+        let size = reader.u16()? as usize;
+        reader.skip(size)?;
+    }
+
+    let mut link = ShellLink::default();
+
+    if flags & HAS_LINK_INFO != 0 && flags & FORCE_NO_LINK_INFO == 0 {
+        let start = reader.position;
+        let size = reader.u32()? as usize;
+        let info = reader.range(start..start + size)?;
+        link.local_path = parse_link_info(info)?;
+        reader.seek(start + size)?;
+    }
+
+    let is_unicode = flags & IS_UNICODE != 0;
+    if flags & HAS_NAME != 0 {
+        reader.string_data(is_unicode)?;
+    }
+    if flags & HAS_RELATIVE_PATH != 0 {
+        link.relative_path = Some(reader.string_data(is_unicode)?);
+    }
+    if flags & HAS_WORKING_DIR != 0 {
+        link.working_dir = Some(reader.string_data(is_unicode)?);
+    }
+    if flags & HAS_ARGUMENTS != 0 {
+        link.arguments = Some(reader.string_data(is_unicode)?);
+    }
+
+    Ok(link)
+}
+
+/// Returns the local base path and common path suffix of a LinkInfo structure
+fn parse_link_info(info: &[u8]) -> Result<Option<String>, String> {
+    let reader = Reader {
+        data: info,
+        position: 0,
+    };
+    let header_size = reader.u32_at(4)? as usize;
+    let flags = reader.u32_at(8)?;
+    if flags & VOLUME_ID_AND_LOCAL_BASE_PATH == 0 {
+        return Ok(None);
+    }
+
+    // Unicode offsets are only present with the larger header
+    let (base_path, suffix) = if header_size >= 0x24 {
+        (
+            reader.utf16z_at(reader.u32_at(0x1C)? as usize)?,
+            reader.utf16z_at(reader.u32_at(0x20)? as usize)?,
+        )
+    } else {
+        (
+            reader.latin1z_at(reader.u32_at(0x10)? as usize)?,
+            reader.latin1z_at(reader.u32_at(0x18)? as usize)?,
+        )
+    };
+
+    if base_path.is_empty() {
+        return Ok(None);
+    }
+    if suffix.is_empty() {
+        return Ok(Some(base_path));
+    }
+    let separator = if base_path.ends_with('\\') { "" } else { "\\" };
+    Ok(Some(format!("{base_path}{separator}{suffix}")))
+}
+
+/// Little endian reader over the shortcut data
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn range(&self, range: Range<usize>) -> Result<&'a [u8], String> {
+        self.data
+            .get(range)
+            .ok_or_else(|| "Truncated shell link".to_string())
+    }
+
+    fn seek(&mut self, position: usize) -> Result<(), String> {
+        if position > self.data.len() {
+            return Err("Truncated shell link".into());
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), String> {
+        self.seek(self.position + count)
+    }
+
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let bytes = self.range(self.position..self.position + count)?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u32_at(&self, offset: usize) -> Result<u32, String> {
+        let bytes = self.range(offset..offset + 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a nul terminated single byte string
+    fn latin1z_at(&self, offset: usize) -> Result<String, String> {
+        let bytes = self.range(offset..self.data.len())?;
+        let end = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or("Unterminated string in shell link")?;
+        Ok(bytes[..end].iter().map(|&byte| char::from(byte)).collect())
+    }
+
+    /// Reads a nul terminated UTF-16 string
+    fn utf16z_at(&self, offset: usize) -> Result<String, String> {
+        let units: Vec<u16> = self
+            .range(offset..self.data.len())?
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .take_while(|&unit| unit != 0)
+            .collect();
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    /// Reads a StringData structure: a character count followed by the characters
+    fn string_data(&mut self, is_unicode: bool) -> Result<String, String> {
+        let count = self.u16()? as usize;
+        if is_unicode {
+            let units: Vec<u16> = self
+                .bytes(count * 2)?
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            Ok(String::from_utf16_lossy(&units))
+        } else {
+            Ok(self
+                .bytes(count)?
+                .iter()
+                .map(|&byte| char::from(byte))
+                .collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(flags: u32) -> Vec<u8> {
+        let mut data = vec![0; HEADER_SIZE];
+        data[..4].copy_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+        data[4..20].copy_from_slice(&LINK_CLSID);
+        data[20..24].copy_from_slice(&flags.to_le_bytes());
+        data
+    }
+
+    /// LinkInfo with an ANSI local base path and an empty common path suffix
+    fn link_info(base_path: &str) -> Vec<u8> {
+        const INFO_HEADER_SIZE: u32 = 0x1C;
+        let base_path_offset = INFO_HEADER_SIZE;
+        let suffix_offset = base_path_offset + base_path.len() as u32 + 1;
+        let size = suffix_offset + 1;
+
+        let mut info = Vec::new();
+        for value in [
+            size,
+            INFO_HEADER_SIZE,
+            VOLUME_ID_AND_LOCAL_BASE_PATH,
+            0, // VolumeIDOffset, the volume is not read
+            base_path_offset,
+            0,
+            suffix_offset,
+        ] {
+            info.extend_from_slice(&value.to_le_bytes());
+        }
+        info.extend_from_slice(base_path.as_bytes());
+        info.extend_from_slice(&[0, 0]);
+        info
+    }
+
+    fn unicode_string(value: &str) -> Vec<u8> {
+        let units: Vec<u16> = value.encode_utf16().collect();
+        let mut data = (units.len() as u16).to_le_bytes().to_vec();
+        for unit in units {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn local_target_with_arguments_and_working_dir() {
+        let mut data = header(
+            HAS_LINK_TARGET_ID_LIST | HAS_LINK_INFO | HAS_WORKING_DIR | HAS_ARGUMENTS | IS_UNICODE,
+        );
+        data.extend_from_slice(&4u16.to_le_bytes());
+        data.extend_from_slice(&[0xAA; 4]);
+        data.extend(link_info(r"C:\Tools\Blish HUD\Blish HUD.exe"));
+        data.extend(unicode_string(r"C:\Tools\Blish HUD"));
+        data.extend(unicode_string("--startgw2 0"));
+
+        let link = parse(&data).unwrap();
+        assert_eq!(
+            link,
+            ShellLink {
+                local_path: Some(r"C:\Tools\Blish HUD\Blish HUD.exe".into()),
+                relative_path: None,
+                working_dir: Some(r"C:\Tools\Blish HUD".into()),
+                arguments: Some("--startgw2 0".into()),
+            }
+        );
+        assert_eq!(
+            link.target(r"C:\Users\me\Desktop").as_deref(),
+            Some(r"C:\Tools\Blish HUD\Blish HUD.exe")
+        );
+    }
+
+    #[test]
+    fn relative_target_is_resolved_against_the_shortcut_directory() {
+        let mut data = header(HAS_NAME | HAS_RELATIVE_PATH);
+        data.extend_from_slice(&[4, 0]);
+        data.extend_from_slice(b"Taco");
+        data.extend_from_slice(&[11, 0]);
+        data.extend_from_slice(b".\\taco.exe\xe9");
+
+        let link = parse(&data).unwrap();
+        assert_eq!(link.relative_path.as_deref(), Some(".\\taco.exe\u{e9}"));
+        assert_eq!(
+            link.target(r"D:\Games\").as_deref(),
+            Some("D:\\Games\\taco.exe\u{e9}")
+        );
+    }
+
+    #[test]
+    fn target_only_in_the_id_list_is_not_resolved() {
+        let mut data = header(HAS_LINK_TARGET_ID_LIST);
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(&[0, 0]);
+
+        assert_eq!(parse(&data).unwrap().target(r"C:\"), None);
+    }
+
+    #[test]
+    fn invalid_data_is_rejected() {
+        assert!(parse(b"MZ\x90\x00").is_err());
+
+        let mut wrong_clsid = header(0);
+        wrong_clsid[4] = 0;
+        assert!(parse(&wrong_clsid).is_err());
+
+        let mut truncated = header(HAS_ARGUMENTS | IS_UNICODE);
+        truncated.extend_from_slice(&[10, 0, b'a', 0]);
+        assert!(parse(&truncated).is_err());
+    }
+}
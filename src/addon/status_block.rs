@@ -0,0 +1,269 @@
+/*!
+
+Status of the executables published in shared memory through the Nexus data link, so other
+addons and plugins can display it without subscribing to events.
+
+The block is shared under [STATUS_BLOCK_RESOURCE] and starts with a [STATUS_BLOCK_VERSION]
+that is incremented whenever the layout changes. Writes are guarded by a sequence lock:
+`sequence` is odd while the block is being written, readers must retry when it is odd or
+changed while they were copying the entries.
+
+The block is rewritten on every process lifecycle change, and once per second to refresh
+the uptimes.
+
+*/
+
+use std::{
+    mem::size_of,
+    ptr,
+    sync::{
+        Mutex,
+        atomic::{AtomicPtr, AtomicU32, Ordering, fence},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use nexus::data_link::share_resource;
+
+use crate::addon::{
+    NexusError, Result,
+    event_payload::{NAME_CAPACITY, StatusCode, encode_str_truncated},
+    manager::{EXE_MANAGER, ExeManager},
+};
+
+/// Identifier of the shared resource
+pub const STATUS_BLOCK_RESOURCE: &str = "EXE_RUNNER_STATUS";
+
+/// Version of the layout of [StatusBlock]
+pub const STATUS_BLOCK_VERSION: u32 = 1;
+
+/// Maximum number of executables in the block, the others are left out
+pub const MAX_ENTRIES: usize = 32;
+
+/// Interval between two refreshes of the uptimes
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Status of one executable
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusEntry {
+    /// Id of the executable
+    pub id: u32,
+    /// [StatusCode] of the executable
+    pub state: u32,
+    /// Process id, 0 when not running
+    pub pid: u32,
+    /// Seconds since the process was launched, 0 when not running
+    pub uptime_seconds: u32,
+    /// Launch time in seconds since the Unix epoch, 0 when not running
+    pub launched_at: u64,
+    /// Display name of the executable as nul terminated UTF-8, truncated to fit
+    pub name: [u8; NAME_CAPACITY],
+}
+
+impl StatusEntry {
+    pub const EMPTY: StatusEntry = StatusEntry {
+        id: 0,
+        state: StatusCode::Stopped as u32,
+        pid: 0,
+        uptime_seconds: 0,
+        launched_at: 0,
+        name: [0; NAME_CAPACITY],
+    };
+}
+
+/// Shared status of every executable
+#[repr(C)]
+#[derive(Debug)]
+pub struct StatusBlock {
+    /// Layout version, [STATUS_BLOCK_VERSION]
+    pub version: u32,
+    /// Sequence lock, odd while the block is being written
+    pub sequence: AtomicU32,
+    /// Number of valid entries
+    pub count: u32,
+    /// Size of one entry in bytes
+    pub entry_size: u32,
+    pub entries: [StatusEntry; MAX_ENTRIES],
+}
+
+// The layout is part of the API, make sure it never changes by accident
+const _: () = assert!(size_of::<StatusEntry>() == 24 + NAME_CAPACITY);
+const _: () = assert!(size_of::<StatusBlock>() == 16 + MAX_ENTRIES * size_of::<StatusEntry>());
+
+impl StatusBlock {
+    pub const fn new() -> Self {
+        Self {
+            version: STATUS_BLOCK_VERSION,
+            sequence: AtomicU32::new(0),
+            count: 0,
+            entry_size: size_of::<StatusEntry>() as u32,
+            entries: [StatusEntry::EMPTY; MAX_ENTRIES],
+        }
+    }
+
+    /// Writes the entries, at most [MAX_ENTRIES], under the sequence lock
+    pub fn publish(&mut self, entries: &[StatusEntry]) {
+        let count = entries.len().min(MAX_ENTRIES);
+
+        self.sequence.fetch_add(1, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        self.version = STATUS_BLOCK_VERSION;
+        self.entry_size = size_of::<StatusEntry>() as u32;
+        self.entries[..count].copy_from_slice(&entries[..count]);
+        self.entries[count..].fill(StatusEntry::EMPTY);
+        self.count = count as u32;
+
+        fence(Ordering::Release);
+        self.sequence.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Reads a consistent copy of the entries, `None` if a write is in progress
+    pub fn read(&self) -> Option<Vec<StatusEntry>> {
+        let before = self.sequence.load(Ordering::Acquire);
+        if before % 2 == 1 {
+            return None;
+        }
+
+        let count = (self.count as usize).min(MAX_ENTRIES);
+        let entries = self.entries[..count].to_vec();
+
+        fence(Ordering::Acquire);
+        (self.sequence.load(Ordering::Relaxed) == before).then_some(entries)
+    }
+}
+
+impl Default for StatusBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Block shared through the data link
+static STATUS_BLOCK: AtomicPtr<StatusBlock> = AtomicPtr::new(ptr::null_mut());
+
+/// Revision of the manager and time of the last publication
+static LAST_PUBLISH: Mutex<Option<(u64, Instant)>> = Mutex::new(None);
+
+/// Allocates the shared status block
+pub fn setup_status_block() -> Result<()> {
+    let block =
+        share_resource(STATUS_BLOCK_RESOURCE, size_of::<StatusBlock>()).cast::<StatusBlock>();
+    if block.is_null() {
+        return Err(NexusError::ResourceLoading(format!(
+            "Failed to share the {STATUS_BLOCK_RESOURCE} resource"
+        )));
+    }
+    // SAFETY: the resource is at least the size of the block and lives until the game exits
+    unsafe { block.write(StatusBlock::new()) };
+    STATUS_BLOCK.store(block, Ordering::Release);
+
+    log::info!("Status block shared as {STATUS_BLOCK_RESOURCE}");
+    Ok(())
+}
+
+/// Publishes the status after a lifecycle change, or once per second. Called every frame.
+pub fn update() {
+    let block = STATUS_BLOCK.load(Ordering::Acquire);
+    if block.is_null() {
+        return;
+    }
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(exe_manager) = exe_manager_arc.lock() else {
+        return;
+    };
+    let Ok(mut last_publish) = LAST_PUBLISH.lock() else {
+        return;
+    };
+
+    let now = Instant::now();
+    let revision = exe_manager.revision();
+    let is_due = last_publish.is_none_or(|(last_revision, last_time)| {
+        last_revision != revision || now.duration_since(last_time) >= REFRESH_INTERVAL
+    });
+    if !is_due {
+        return;
+    }
+    *last_publish = Some((revision, now));
+
+    let entries = status_entries(&exe_manager, SystemTime::now());
+    // SAFETY: the block was initialized in `setup_status_block` and is only written here
+    unsafe { (*block).publish(&entries) };
+}
+
+fn status_entries(exe_manager: &ExeManager, now: SystemTime) -> Vec<StatusEntry> {
+    exe_manager
+        .executables()
+        .iter()
+        .map(|exe| {
+            let state = exe_manager.status_code(&exe.path);
+            let launched_at = exe_manager.launched_at(&exe.path);
+            let uptime = launched_at
+                .and_then(|launched_at| now.duration_since(launched_at).ok())
+                .unwrap_or_default();
+            StatusEntry {
+                id: exe.id,
+                state: state as u32,
+                pid: exe_manager.pid(&exe.path).unwrap_or(0),
+                uptime_seconds: u32::try_from(uptime.as_secs()).unwrap_or(u32::MAX),
+                launched_at: launched_at
+                    .and_then(|launched_at| launched_at.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |since_epoch| since_epoch.as_secs()),
+                name: encode_str_truncated(exe.display_name()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u32, state: StatusCode) -> StatusEntry {
+        StatusEntry {
+            id,
+            state: state as u32,
+            pid: id * 100,
+            uptime_seconds: 5,
+            launched_at: 1_700_000_000,
+            name: encode_str_truncated(&format!("tool {id}")),
+        }
+    }
+
+    #[test]
+    fn published_entries_are_read_back() {
+        let mut block = StatusBlock::new();
+        let entries = [entry(1, StatusCode::Running), entry(2, StatusCode::Stopped)];
+
+        block.publish(&entries);
+        assert_eq!(block.read().unwrap(), entries);
+        assert_eq!(block.sequence.load(Ordering::Relaxed), 2);
+        assert_eq!(block.version, STATUS_BLOCK_VERSION);
+
+        block.publish(&entries[..1]);
+        assert_eq!(block.read().unwrap(), entries[..1]);
+        assert_eq!(block.entries[1], StatusEntry::EMPTY);
+    }
+
+    #[test]
+    fn entries_beyond_the_capacity_are_left_out() {
+        let mut block = StatusBlock::new();
+        let entries: Vec<_> = (0..MAX_ENTRIES as u32 + 5)
+            .map(|id| entry(id, StatusCode::Running))
+            .collect();
+
+        block.publish(&entries);
+        assert_eq!(block.count as usize, MAX_ENTRIES);
+        assert_eq!(block.read().unwrap(), entries[..MAX_ENTRIES]);
+    }
+
+    #[test]
+    fn reads_during_a_write_fail() {
+        let block = StatusBlock::new();
+        block.sequence.store(1, Ordering::Relaxed);
+        assert_eq!(block.read(), None);
+    }
+}
@@ -0,0 +1,195 @@
+/*!
+
+Per-frame update driving the automatic behaviours of the runner from the game state.
+
+Reads MumbleLink every frame to apply the executables' map triggers whenever the player
+changes map, and their game transition triggers when entering the game or returning to
+character select. Scheduled launches are checked once per second, and focus changes
+are applied once they lasted for their grace period. Process state changes are raised
+as events for other addons and published in the shared status block.
+
+*/
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use chrono::NaiveDateTime;
+
+use nexus::{
+    data_link::read_mumble_link,
+    gui::{RenderType, register_render},
+    render,
+};
+
+use crate::addon::{
+    events, focus,
+    game_state::{CHARACTER_SELECT_DEBOUNCE, GameEvent, GameState, GameStateTracker},
+    manager::EXE_MANAGER,
+    schedule::{Scheduler, SystemClock},
+    status_block,
+    triggers::MapSnapshot,
+};
+
+/// Interval between two checks of the scheduled launches
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Map the triggers were last evaluated on
+static LAST_MAP: Mutex<Option<MapSnapshot>> = Mutex::new(None);
+
+/// Character select / loading screen / in-game tracking
+static GAME_STATE: Mutex<GameStateTracker> =
+    Mutex::new(GameStateTracker::new(CHARACTER_SELECT_DEBOUNCE));
+
+/// Next runs of the scheduled executables
+static SCHEDULER: Mutex<Scheduler<SystemClock>> = Mutex::new(Scheduler::new(SystemClock));
+
+/// Time the scheduled launches were last checked
+static LAST_SCHEDULE_CHECK: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Registers the per-frame update callback with nexus
+pub fn setup_tick() {
+    let tick = render!(|_ui| {
+        tick();
+    });
+    register_render(RenderType::PreRender, tick).revert_on_unload();
+}
+
+/// Returns the map the player is currently on, if MumbleLink has been populated
+pub fn current_map() -> Option<MapSnapshot> {
+    LAST_MAP.lock().ok().and_then(|map| *map)
+}
+
+/// Returns the next scheduled run of an executable
+pub fn next_scheduled_run(path: &str) -> Option<NaiveDateTime> {
+    SCHEDULER.lock().ok()?.next_run(path)
+}
+
+/// Returns where the player currently is
+pub fn current_game_state() -> Option<GameState> {
+    GAME_STATE.lock().ok().map(|tracker| tracker.state())
+}
+
+fn tick() {
+    check_schedules();
+    events::update();
+    status_block::update();
+    focus::update();
+
+    let Some(link) = read_mumble_link() else {
+        return;
+    };
+
+    let event = GAME_STATE
+        .lock()
+        .ok()
+        .and_then(|mut tracker| tracker.update(link.ui_tick, Instant::now()));
+    if let Some(event) = event {
+        on_game_event(event);
+    }
+
+    let current = MapSnapshot {
+        map_id: link.context.map_id,
+        map_type: link.context.map_type,
+    };
+    on_map(current);
+}
+
+fn on_game_event(event: GameEvent) {
+    log::info!("Game transition: {event:?}");
+
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        log::error!("Failed to lock exe manager on game transition");
+        return;
+    };
+
+    exe_manager.cleanup_finished_processes();
+    exe_manager.apply_game_event(event);
+}
+
+fn on_map(current: MapSnapshot) {
+    // MumbleLink is zeroed until the first map is loaded
+    if current.map_id == 0 {
+        return;
+    }
+
+    let previous = {
+        let Ok(mut last_map) = LAST_MAP.lock() else {
+            return;
+        };
+        if *last_map == Some(current) {
+            return;
+        }
+        last_map.replace(current)
+    };
+
+    log::debug!(
+        "Map changed to {} (type {})",
+        current.map_id,
+        current.map_type
+    );
+
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        log::error!("Failed to lock exe manager on map change");
+        return;
+    };
+
+    exe_manager.cleanup_finished_processes();
+    exe_manager.apply_map_triggers(previous.as_ref(), &current);
+}
+
+fn check_schedules() {
+    {
+        let Ok(mut last_check) = LAST_SCHEDULE_CHECK.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        if last_check.is_some_and(|last| now.duration_since(last) < SCHEDULE_CHECK_INTERVAL) {
+            return;
+        }
+        *last_check = Some(now);
+    }
+
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        log::error!("Failed to lock exe manager for scheduled launches");
+        return;
+    };
+    let Ok(mut scheduler) = SCHEDULER.lock() else {
+        return;
+    };
+
+    scheduler.sync(
+        exe_manager
+            .executables()
+            .iter()
+            .filter(|exe| exe_manager.is_enabled(exe))
+            .map(|exe| (exe.path.as_str(), exe.schedule.as_ref())),
+    );
+    let due = scheduler.take_due();
+    drop(scheduler);
+
+    if due.is_empty() {
+        return;
+    }
+    exe_manager.cleanup_finished_processes();
+    for path in due {
+        if exe_manager.is_running(&path) {
+            log::info!("Scheduled run of {path} skipped, it is still running");
+            continue;
+        }
+        log::info!("Launching scheduled executable: {path}");
+        if let Err(e) = exe_manager.launch_exe_unattended(&path) {
+            log::warn!("Scheduled launch failed for {path}: {e}");
+        }
+    }
+}
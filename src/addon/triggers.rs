@@ -0,0 +1,400 @@
+/*!
+
+Map and game mode based launch triggers.
+
+This module is pure: it only evaluates trigger conditions against snapshots of the MumbleLink
+map context and decides what should happen to an executable. Reading MumbleLink and acting on
+the decisions is done by the caller.
+
+*/
+
+use serde::{Deserialize, Serialize};
+
+/// Subset of the MumbleLink context the triggers are evaluated against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MapSnapshot {
+    pub map_id: u32,
+    pub map_type: u32,
+}
+
+/// Broad game mode derived from the MumbleLink map type
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// Open world maps
+    Pve,
+    /// World vs World maps, including Edge of the Mists and Armistice Bastion
+    Wvw,
+    /// Structured PvP maps and the Heart of the Mists
+    Pvp,
+    /// Instanced content such as raids, fractals, strikes and story instances
+    Instance,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Pve,
+        GameMode::Wvw,
+        GameMode::Pvp,
+        GameMode::Instance,
+    ];
+
+    /// Maps a MumbleLink map type to a game mode, `None` for character creation and redirects
+    pub fn from_map_type(map_type: u32) -> Option<GameMode> {
+        match map_type {
+            5 | 7 | 16 => Some(GameMode::Pve),
+            9..=15 | 18 => Some(GameMode::Wvw),
+            2 | 6 | 8 => Some(GameMode::Pvp),
+            4 => Some(GameMode::Instance),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Pve => "PvE",
+            GameMode::Wvw => "WvW",
+            GameMode::Pvp => "PvP",
+            GameMode::Instance => "Instance",
+        }
+    }
+}
+
+/// A single condition on the current map
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerCondition {
+    /// The current map belongs to a game mode
+    GameMode { mode: GameMode },
+    /// The current map is one of the listed map ids
+    MapIds { ids: Vec<u32> },
+}
+
+impl TriggerCondition {
+    pub fn matches(&self, snapshot: &MapSnapshot) -> bool {
+        match self {
+            TriggerCondition::GameMode { mode } => {
+                GameMode::from_map_type(snapshot.map_type) == Some(*mode)
+            }
+            TriggerCondition::MapIds { ids } => ids.contains(&snapshot.map_id),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            TriggerCondition::GameMode { mode } => format!("In {} map", mode.label()),
+            TriggerCondition::MapIds { ids } => {
+                let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+                format!("In map {}", ids.join(", "))
+            }
+        }
+    }
+}
+
+/// Per-executable map triggers: launch when entering a matching map,
+/// optionally stop when leaving it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct MapTriggers {
+    /// The executable is wanted while any of the conditions matches
+    #[serde(default)]
+    pub conditions: Vec<TriggerCondition>,
+    /// Stop the executable when leaving the matching maps
+    #[serde(default)]
+    pub stop_on_exit: bool,
+    /// Suspend the executable when leaving the matching maps and resume it when
+    /// entering one again, instead of stopping and launching it
+    #[serde(default)]
+    pub suspend_on_exit: bool,
+}
+
+impl MapTriggers {
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Returns whether any condition matches the snapshot
+    pub fn matches(&self, snapshot: &MapSnapshot) -> bool {
+        self.conditions
+            .iter()
+            .any(|condition| condition.matches(snapshot))
+    }
+}
+
+/// What a trigger evaluation asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerAction {
+    Launch,
+    Stop,
+    Suspend,
+    Resume,
+    Nothing,
+}
+
+/**
+ * Decides what to do with an executable when the map changes.
+ *
+ * Triggers are edge based: an executable is launched (or resumed) when entering a matching
+ * map and stopped (or suspended) when leaving one, so stopping or launching it manually while
+ * staying on the same map is not overridden.
+ *
+ * # Arguments
+ * * `triggers` - Triggers of the executable
+ * * `previous` - Map before the change, `None` on the first evaluation
+ * * `current` - Map after the change
+ * * `is_running` - Whether the executable is currently running
+ * * `is_suspended` - Whether the running executable is currently suspended
+ */
+pub fn decide(
+    triggers: &MapTriggers,
+    previous: Option<&MapSnapshot>,
+    current: &MapSnapshot,
+    is_running: bool,
+    is_suspended: bool,
+) -> TriggerAction {
+    if triggers.is_empty() {
+        return TriggerAction::Nothing;
+    }
+
+    let was_matching = previous.is_some_and(|previous| triggers.matches(previous));
+    let is_matching = triggers.matches(current);
+
+    match (was_matching, is_matching) {
+        (false, true) if is_suspended => TriggerAction::Resume,
+        (false, true) if !is_running => TriggerAction::Launch,
+        (true, false) if is_running && !is_suspended && triggers.suspend_on_exit => {
+            TriggerAction::Suspend
+        }
+        (true, false) if is_running && triggers.stop_on_exit && !triggers.suspend_on_exit => {
+            TriggerAction::Stop
+        }
+        _ => TriggerAction::Nothing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Snapshots recorded from MumbleLink on live maps
+    const LIONS_ARCH: MapSnapshot = MapSnapshot {
+        map_id: 50,
+        map_type: 5,
+    };
+    const ETERNAL_BATTLEGROUNDS: MapSnapshot = MapSnapshot {
+        map_id: 38,
+        map_type: 9,
+    };
+    const RED_BORDERLANDS: MapSnapshot = MapSnapshot {
+        map_id: 1099,
+        map_type: 12,
+    };
+    const ARMISTICE_BASTION: MapSnapshot = MapSnapshot {
+        map_id: 1318,
+        map_type: 18,
+    };
+    const HEART_OF_THE_MISTS: MapSnapshot = MapSnapshot {
+        map_id: 350,
+        map_type: 2,
+    };
+    const SPIRIT_VALE: MapSnapshot = MapSnapshot {
+        map_id: 1062,
+        map_type: 4,
+    };
+    const CHARACTER_SELECT: MapSnapshot = MapSnapshot {
+        map_id: 0,
+        map_type: 1,
+    };
+
+    fn wvw_triggers(stop_on_exit: bool) -> MapTriggers {
+        MapTriggers {
+            conditions: vec![TriggerCondition::GameMode {
+                mode: GameMode::Wvw,
+            }],
+            stop_on_exit,
+            suspend_on_exit: false,
+        }
+    }
+
+    #[test]
+    fn game_mode_from_map_type() {
+        assert_eq!(
+            GameMode::from_map_type(LIONS_ARCH.map_type),
+            Some(GameMode::Pve)
+        );
+        assert_eq!(
+            GameMode::from_map_type(ETERNAL_BATTLEGROUNDS.map_type),
+            Some(GameMode::Wvw)
+        );
+        assert_eq!(
+            GameMode::from_map_type(ARMISTICE_BASTION.map_type),
+            Some(GameMode::Wvw)
+        );
+        assert_eq!(
+            GameMode::from_map_type(HEART_OF_THE_MISTS.map_type),
+            Some(GameMode::Pvp)
+        );
+        assert_eq!(
+            GameMode::from_map_type(SPIRIT_VALE.map_type),
+            Some(GameMode::Instance)
+        );
+        assert_eq!(GameMode::from_map_type(CHARACTER_SELECT.map_type), None);
+    }
+
+    #[test]
+    fn map_id_condition() {
+        let condition = TriggerCondition::MapIds {
+            ids: vec![SPIRIT_VALE.map_id],
+        };
+        assert!(condition.matches(&SPIRIT_VALE));
+        assert!(!condition.matches(&LIONS_ARCH));
+    }
+
+    #[test]
+    fn launches_when_entering_matching_map() {
+        let triggers = wvw_triggers(false);
+        assert_eq!(
+            decide(
+                &triggers,
+                Some(&LIONS_ARCH),
+                &ETERNAL_BATTLEGROUNDS,
+                false,
+                false
+            ),
+            TriggerAction::Launch
+        );
+        assert_eq!(
+            decide(&triggers, None, &ETERNAL_BATTLEGROUNDS, false, false),
+            TriggerAction::Launch
+        );
+        assert_eq!(
+            decide(
+                &triggers,
+                Some(&LIONS_ARCH),
+                &ETERNAL_BATTLEGROUNDS,
+                true,
+                false
+            ),
+            TriggerAction::Nothing
+        );
+    }
+
+    #[test]
+    fn moving_between_matching_maps_does_nothing() {
+        let triggers = wvw_triggers(true);
+        assert_eq!(
+            decide(
+                &triggers,
+                Some(&ETERNAL_BATTLEGROUNDS),
+                &RED_BORDERLANDS,
+                false,
+                false
+            ),
+            TriggerAction::Nothing
+        );
+    }
+
+    #[test]
+    fn stops_when_leaving_only_if_requested() {
+        assert_eq!(
+            decide(
+                &wvw_triggers(true),
+                Some(&RED_BORDERLANDS),
+                &LIONS_ARCH,
+                true,
+                false
+            ),
+            TriggerAction::Stop
+        );
+        assert_eq!(
+            decide(
+                &wvw_triggers(false),
+                Some(&RED_BORDERLANDS),
+                &LIONS_ARCH,
+                true,
+                false
+            ),
+            TriggerAction::Nothing
+        );
+        assert_eq!(
+            decide(
+                &wvw_triggers(true),
+                Some(&RED_BORDERLANDS),
+                &LIONS_ARCH,
+                false,
+                false
+            ),
+            TriggerAction::Nothing
+        );
+    }
+
+    #[test]
+    fn suspends_on_exit_and_resumes_on_entry() {
+        let triggers = MapTriggers {
+            suspend_on_exit: true,
+            ..wvw_triggers(true)
+        };
+        assert_eq!(
+            decide(&triggers, Some(&RED_BORDERLANDS), &LIONS_ARCH, true, false),
+            TriggerAction::Suspend
+        );
+        assert_eq!(
+            decide(&triggers, Some(&RED_BORDERLANDS), &LIONS_ARCH, true, true),
+            TriggerAction::Nothing
+        );
+        assert_eq!(
+            decide(&triggers, Some(&LIONS_ARCH), &RED_BORDERLANDS, true, true),
+            TriggerAction::Resume
+        );
+    }
+
+    #[test]
+    fn any_condition_matches() {
+        let triggers = MapTriggers {
+            conditions: vec![
+                TriggerCondition::GameMode {
+                    mode: GameMode::Pvp,
+                },
+                TriggerCondition::MapIds {
+                    ids: vec![SPIRIT_VALE.map_id],
+                },
+            ],
+            stop_on_exit: true,
+            suspend_on_exit: false,
+        };
+        assert!(triggers.matches(&HEART_OF_THE_MISTS));
+        assert!(triggers.matches(&SPIRIT_VALE));
+        assert!(!triggers.matches(&LIONS_ARCH));
+    }
+
+    #[test]
+    fn empty_triggers_never_act() {
+        let triggers = MapTriggers::default();
+        assert_eq!(
+            decide(&triggers, Some(&LIONS_ARCH), &SPIRIT_VALE, false, false),
+            TriggerAction::Nothing
+        );
+    }
+
+    #[test]
+    fn conditions_round_trip_through_json() {
+        let triggers = MapTriggers {
+            conditions: vec![
+                TriggerCondition::GameMode {
+                    mode: GameMode::Instance,
+                },
+                TriggerCondition::MapIds { ids: vec![1062] },
+            ],
+            stop_on_exit: true,
+            suspend_on_exit: false,
+        };
+        let json = serde_json::to_string(&triggers).unwrap();
+        assert_eq!(
+            json,
+            r#"{"conditions":[{"type":"game_mode","mode":"instance"},{"type":"map_ids","ids":[1062]}],"stop_on_exit":true,"suspend_on_exit":false}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<MapTriggers>(&json).unwrap(),
+            triggers
+        );
+    }
+}
@@ -5,23 +5,96 @@ This module contains all Nexus-specific UI rendering logic and components for th
 ## Components
 
 - Main window rendering
-- Executable list and controls
+- Profile selector and automatic selection bindings
+- Executable list and controls, organized in collapsible groups, with the live usage and readiness of running executables
 - Add executable dialog
+- Edit executable modal, including the run as administrator option, the map trigger rules, priority, CPU affinity and resource limit, health check and readiness editors
 - Control buttons (Stop All, Running Count)
+- Quick access context menu
+- History tab with the launches, exits and interventions of the runner
+- Environment tab with the wine/Proton diagnostics
 
 */
 
-use crate::addon::manager::{EXE_MANAGER, ExeManager, open_file_dialog};
+use crate::addon::{
+    diagnostics,
+    focus::FocusPolicy,
+    game_state::GameEvent,
+    health::{HealthCheck, HealthProbe, HealthStatus},
+    identity::{CURRENT_IDENTITY, PROFESSIONS},
+    keybinds,
+    limits::{LimitAction, MIN_DURATION_SECS, ResourceLimits},
+    manager::{EXE_MANAGER, ExeKind, ExeManager, ExeState, Executable, open_file_dialog},
+    monitor,
+    process::{self, Priority},
+    readiness::{Readiness, ReadinessProbe, ReadyState},
+    schedule::Schedule,
+    tick,
+    triggers::{GameMode, TriggerCondition},
+    usage::{format_bytes, format_uptime},
+    wine, wine_path,
+};
+use chrono::{DateTime, Local, NaiveDateTime};
 use nexus::{
     gui::register_render,
-    imgui::{Ui, Window},
+    imgui::{
+        CollapsingHeader, ComboBox, Direction, DragDropFlags, DragDropSource, DragDropTarget,
+        PopupModal, Selectable, TabBar, TabItem, Ui, Window,
+    },
     render,
 };
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 /// Global state for tracking if the main window is open
 pub static IS_WINDOW_OPEN: AtomicBool = AtomicBool::new(false);
 
+/// Name typed in the "Add group" input
+static NEW_GROUP_NAME: Mutex<String> = Mutex::new(String::new());
+
+/// Name typed in the "New profile" input
+static NEW_PROFILE_NAME: Mutex<String> = Mutex::new(String::new());
+
+/// Path typed in the "Native Linux program" input, only shown under wine
+static NEW_UNIX_PATH: Mutex<String> = Mutex::new(String::new());
+
+/// Link typed in the "Add link" input
+static NEW_URL: Mutex<String> = Mutex::new(String::new());
+
+/// Draft of the automatic selection bindings of the active profile
+static BINDINGS_DRAFT: Mutex<Option<BindingsDraft>> = Mutex::new(None);
+
+/// Comma separated character and account names being edited for a profile
+struct BindingsDraft {
+    profile: String,
+    characters: String,
+    accounts: String,
+}
+
+/// State of the edit modal, `None` when closed
+static EDIT_STATE: Mutex<Option<EditState>> = Mutex::new(None);
+
+/// Popup identifier of the edit modal
+const EDIT_POPUP: &str = "Edit executable";
+
+/// Draft values edited in the edit modal, applied on save
+struct EditState {
+    index: usize,
+    draft: Executable,
+    /// Environment variables as `KEY=VALUE` lines
+    env_text: String,
+    /// Comma separated map ids of the map trigger being added
+    map_ids_text: String,
+    /// Index in `GameMode::ALL` of the game mode trigger being added
+    trigger_mode: usize,
+    error: Option<String>,
+}
+
 /// Registers the main window rendering callback with nexus
 pub fn setup_main_window_rendering() {
     let main_window = render!(|ui| {
@@ -30,6 +103,84 @@ pub fn setup_main_window_rendering() {
     register_render(nexus::gui::RenderType::Render, main_window).revert_on_unload();
 }
 
+/**
+ * Renders the quick access context menu: every executable with its state and
+ * a Launch/Stop toggle, followed by a Stop all button.
+ */
+pub fn render_quick_access_menu(ui: &Ui) {
+    let Some(exe_manager_arc) = EXE_MANAGER.get() else {
+        return;
+    };
+    let Ok(mut exe_manager) = exe_manager_arc.lock() else {
+        ui.text_colored([1.0, 0.0, 0.0, 1.0], "Error: Could not lock exe manager");
+        return;
+    };
+    exe_manager.cleanup_finished_processes();
+
+    if exe_manager.executables().is_empty() {
+        ui.text_disabled("No executables added yet");
+    }
+
+    let mut toggle = None;
+    for exe in exe_manager.executables() {
+        let _id = ui.push_id(exe.path.as_str());
+        let state = exe_manager.state(&exe.path);
+
+        if exe.kind == ExeKind::Url {
+            if ui.small_button("Open") {
+                toggle = Some(exe.id);
+            }
+            ui.same_line();
+            render_link_icon(ui);
+            ui.same_line();
+            ui.text(truncate_middle(&shown_name(exe), 40));
+            continue;
+        }
+
+        let label = if state == ExeState::Stopped {
+            "Launch"
+        } else {
+            "Stop"
+        };
+        // Native Linux programs can't be stopped from here
+        let can_toggle = state == ExeState::Stopped || exe.kind.tracks_program();
+        ui.disabled(!can_toggle, || {
+            if ui.small_button(label) {
+                toggle = Some(exe.id);
+            }
+        });
+        ui.same_line();
+        let unhealthy = exe_manager
+            .health(&exe.path)
+            .is_some_and(|health| health.failures() > 0);
+        match state {
+            ExeState::Running if unhealthy => ui.text_colored([1.0, 0.3, 0.3, 1.0], "Unhealthy"),
+            ExeState::Running => ui.text_colored([0.0, 1.0, 0.0, 1.0], "Running"),
+            ExeState::Suspended => ui.text_colored([1.0, 0.8, 0.0, 1.0], "Suspended"),
+            ExeState::Stopped => ui.text_colored([0.5, 0.5, 0.5, 1.0], "Stopped"),
+        }
+        ui.same_line();
+        ui.text(truncate_middle(&shown_name(exe), 40));
+    }
+
+    ui.separator();
+    if ui.button("Stop all") {
+        if let Err(e) = exe_manager.stop_all() {
+            log::error!("Failed to stop all executables: {e}");
+        }
+    }
+    ui.same_line();
+    if ui.button("Open window") {
+        toggle_window();
+    }
+
+    if let Some(id) = toggle {
+        if let Err(e) = exe_manager.toggle_exe(id) {
+            log::error!("Failed to toggle executable: {e}");
+        }
+    }
+}
+
 /// Renders the main window
 pub fn render_main_window(ui: &Ui) {
     let mut is_open = IS_WINDOW_OPEN.load(Ordering::Relaxed);
@@ -56,10 +207,78 @@ fn render_window_content(ui: &Ui) {
         // Cleanup finished processes
         exe_manager.cleanup_finished_processes();
 
-        render_header(ui);
-        render_add_executable_section(ui, &mut exe_manager);
-        render_executable_list(ui, &mut exe_manager);
-        render_control_buttons(ui, &mut exe_manager);
+        TabBar::new("##main_tabs").build(ui, || {
+            TabItem::new("Executables").build(ui, || {
+                render_header(ui);
+                render_profile_selector(ui, &mut exe_manager);
+                render_add_executable_section(ui, &mut exe_manager);
+                render_executable_list(ui, &mut exe_manager);
+                render_control_buttons(ui, &mut exe_manager);
+            });
+            TabItem::new("History").build(ui, || {
+                render_history_tab(ui, &exe_manager);
+            });
+            TabItem::new("Environment").build(ui, || {
+                render_environment_tab(ui);
+            });
+        });
+    }
+}
+
+/// Renders the run history, newest first
+fn render_history_tab(ui: &Ui, exe_manager: &ExeManager) {
+    if exe_manager.run_history().is_empty() {
+        ui.text_disabled("Nothing launched yet");
+        return;
+    }
+    for record in exe_manager.run_history().iter().rev() {
+        let at: DateTime<Local> = record.at.into();
+        ui.text_disabled(at.format("%H:%M:%S").to_string());
+        ui.same_line();
+        ui.text_wrapped(format!("{}: {}", record.name, record.message));
+    }
+}
+
+/// Renders the wine/Proton environment diagnostics
+fn render_environment_tab(ui: &Ui) {
+    let diagnostics = diagnostics::current();
+    let or_none = |value: &Option<String>| value.as_deref().unwrap_or("none").to_string();
+
+    if diagnostics.is_wine() {
+        ui.text_colored([0.0, 1.0, 0.0, 1.0], "Running under wine");
+    } else {
+        ui.text("Running on Windows");
+    }
+    ui.separator();
+
+    ui.text(format!(
+        "Wine version: {}",
+        or_none(&diagnostics.wine_version)
+    ));
+    ui.text(format!("Proton: {}", or_none(&diagnostics.proton_version)));
+    ui.text_wrapped(format!("Prefix: {}", or_none(&diagnostics.prefix)));
+    ui.text(format!("Host: {}", diagnostics.host_os));
+    ui.text(format!(
+        "Steam Deck: {}",
+        if diagnostics.steam_deck { "yes" } else { "no" }
+    ));
+    ui.text(format!(
+        "Gamescope: {}",
+        if diagnostics.gamescope { "yes" } else { "no" }
+    ));
+
+    ui.separator();
+    ui.text("Environment variables:");
+    if diagnostics.variables.is_empty() {
+        ui.text_disabled("None set");
+    }
+    for (name, value) in &diagnostics.variables {
+        ui.bullet_text(format!("{name}={value}"));
+    }
+
+    ui.separator();
+    if ui.button("Copy to clipboard") {
+        ui.set_clipboard_text(diagnostics.report());
     }
 }
 
@@ -71,57 +290,367 @@ fn render_header(ui: &Ui) {
     ui.separator();
 }
 
+/// Renders the profile selector and the profile management controls
+fn render_profile_selector(ui: &Ui, exe_manager: &mut ExeManager) {
+    const ALL_EXECUTABLES: &str = "All executables";
+
+    let active = exe_manager
+        .active_profile()
+        .map(|profile| profile.name.clone());
+    let names: Vec<String> = exe_manager
+        .profiles()
+        .iter()
+        .map(|profile| profile.name.clone())
+        .collect();
+
+    let mut selected = None;
+    ComboBox::new("Profile")
+        .preview_value(active.as_deref().unwrap_or(ALL_EXECUTABLES))
+        .build(ui, || {
+            if Selectable::new(ALL_EXECUTABLES)
+                .selected(active.is_none())
+                .build(ui)
+            {
+                selected = Some(None);
+            }
+            for name in &names {
+                if Selectable::new(name.as_str())
+                    .selected(active.as_ref() == Some(name))
+                    .build(ui)
+                {
+                    selected = Some(Some(name.clone()));
+                }
+            }
+        });
+
+    if let Some(name) = selected {
+        if let Err(e) = exe_manager.switch_profile(name.as_deref()) {
+            log::error!("Failed to switch profile: {e}");
+        }
+    }
+
+    if let Some(name) = &active {
+        ui.same_line();
+        if ui.button("Delete profile") {
+            if let Err(e) = exe_manager.remove_profile(name) {
+                log::error!("Failed to remove profile: {e}");
+            }
+        }
+    }
+
+    render_profile_bindings(ui, exe_manager);
+
+    if let Ok(mut name) = NEW_PROFILE_NAME.lock() {
+        ui.input_text("##new_profile_name", &mut name).build();
+        ui.same_line();
+        if ui.button("New profile") {
+            match exe_manager.add_profile(&name) {
+                Ok(()) => name.clear(),
+                Err(e) => log::error!("Failed to add profile: {e}"),
+            }
+        }
+    }
+
+    ui.separator();
+}
+
+/// Renders the character, profession and account bindings of the active profile
+fn render_profile_bindings(ui: &Ui, exe_manager: &mut ExeManager) {
+    let Some(profile) = exe_manager.active_profile().cloned() else {
+        return;
+    };
+    if !CollapsingHeader::new("Automatic selection").build(ui) {
+        return;
+    }
+    let Ok(mut draft) = BINDINGS_DRAFT.lock() else {
+        return;
+    };
+
+    // Reset the draft when the active profile changed
+    if draft.as_ref().is_none_or(|d| d.profile != profile.name) {
+        *draft = Some(BindingsDraft {
+            profile: profile.name.clone(),
+            characters: profile.characters.join(", "),
+            accounts: profile.accounts.join(", "),
+        });
+    }
+    let Some(draft) = draft.as_mut() else {
+        return;
+    };
+
+    ui.text_wrapped("This profile is selected automatically when logging in with:");
+    ui.input_text("Characters", &mut draft.characters).build();
+    let current = CURRENT_IDENTITY
+        .lock()
+        .ok()
+        .map(|identity| identity.clone());
+    if let Some(character) = current.as_ref().and_then(|i| i.character.as_deref()) {
+        ui.same_line();
+        if ui.button("Add current") {
+            if !draft.characters.trim().is_empty() {
+                draft.characters.push_str(", ");
+            }
+            draft.characters.push_str(character);
+        }
+    }
+    ui.input_text("Accounts", &mut draft.accounts).build();
+    if let Some(account) = current.as_ref().and_then(|i| i.account.as_deref()) {
+        ui.same_line();
+        if ui.button("Add current##account") {
+            if !draft.accounts.trim().is_empty() {
+                draft.accounts.push_str(", ");
+            }
+            draft.accounts.push_str(account);
+        }
+    }
+
+    let mut professions = profile.professions.clone();
+    ui.text("Professions:");
+    for (i, name) in PROFESSIONS.iter().enumerate() {
+        let id = i as u32 + 1;
+        let mut selected = professions.contains(&id);
+        if i % 3 != 0 {
+            ui.same_line();
+        }
+        if ui.checkbox(name, &mut selected) {
+            professions.retain(|&p| p != id);
+            if selected {
+                professions.push(id);
+            }
+        }
+    }
+
+    let split = |text: &str| -> Vec<String> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let professions_changed = professions != profile.professions;
+    if ui.button("Apply bindings") || professions_changed {
+        if let Err(e) = exe_manager.set_profile_bindings(
+            &profile.name,
+            split(&draft.characters),
+            professions,
+            split(&draft.accounts),
+        ) {
+            log::error!("Failed to update profile bindings: {e}");
+        }
+    }
+}
+
 /// Renders the section for adding new executables
 fn render_add_executable_section(ui: &Ui, exe_manager: &mut ExeManager) {
     ui.text("Add New Executable:");
 
     if ui.button("Browse for Executable...") {
         if let Some(selected_path) = open_file_dialog() {
-            if let Err(e) = exe_manager.add_exe(selected_path) {
-                log::error!("Failed to add executable: {e}");
+            match exe_manager.add_exe(selected_path) {
+                Ok(id) => {
+                    if let Some(exe) = exe_manager.executable_by_id(id) {
+                        keybinds::register_exe_keybind(exe);
+                    }
+                }
+                Err(e) => log::error!("Failed to add executable: {e}"),
             }
         }
     }
 
     ui.same_line();
     ui.text_wrapped("Click 'Browse' to select an executable file");
+
+    // Native Linux programs can't be picked from the Windows file dialog
+    if wine::is_wine() {
+        if let Ok(mut unix_path) = NEW_UNIX_PATH.lock() {
+            ui.input_text("##new_unix_path", &mut unix_path)
+                .hint("/usr/bin/obs")
+                .build();
+            ui.same_line();
+            if ui.button("Add Linux program") {
+                match exe_manager
+                    .add_exe_of_kind(unix_path.trim().to_string(), ExeKind::LinuxNative)
+                {
+                    Ok(id) => {
+                        if let Some(exe) = exe_manager.executable_by_id(id) {
+                            keybinds::register_exe_keybind(exe);
+                        }
+                        unix_path.clear();
+                    }
+                    Err(e) => log::error!("Failed to add Linux program: {e}"),
+                }
+            }
+        }
+    }
+
+    if let Ok(mut url) = NEW_URL.lock() {
+        ui.input_text("##new_url", &mut url)
+            .hint("https://gw2efficiency.com")
+            .build();
+        ui.same_line();
+        if ui.button("Add link") {
+            match exe_manager.add_exe_of_kind(url.trim().to_string(), ExeKind::Url) {
+                Ok(id) => {
+                    if let Some(exe) = exe_manager.executable_by_id(id) {
+                        keybinds::register_exe_keybind(exe);
+                    }
+                    url.clear();
+                }
+                Err(e) => log::error!("Failed to add link: {e}"),
+            }
+        }
+    }
     ui.separator();
 }
 
-/// Renders the list of executables with their controls
+/// Drag and drop payload identifier for executable entries
+const EXE_PAYLOAD: &str = "GW2_EXECUTABLE_RUNNER_EXE";
+
+/// Actions collected while rendering the list, applied once rendering is done
+#[derive(Default)]
+struct PendingActions {
+    launch: Option<String>,
+    stop: Option<String>,
+    suspend: Option<String>,
+    resume: Option<String>,
+    remove: Option<usize>,
+    edit: Option<usize>,
+    move_to_group: Option<(usize, Option<String>)>,
+    /// (from, to, group of the entry dropped on)
+    reorder: Option<(usize, usize, Option<String>)>,
+    group: Option<GroupAction>,
+}
+
+/// Group-level actions triggered from a group header
+enum GroupAction {
+    LaunchAll(Option<String>),
+    StopAll(Option<String>),
+    SetLaunchOnStartup(String, bool),
+    Remove(String),
+}
+
+/// Renders the list of executables, one collapsible section per group
 fn render_executable_list(ui: &Ui, exe_manager: &mut ExeManager) {
     ui.text("Executable List:");
 
-    // Track actions to perform after the loop
-    let mut to_remove = None;
-    let mut to_stop = None;
-    let mut to_launch = None;
+    let mut actions = PendingActions::default();
 
-    // Get the number of executables
-    let exe_count = exe_manager.executables().len();
-
-    if exe_count == 0 {
+    if exe_manager.executables().is_empty() {
         ui.text_colored([0.6, 0.6, 0.6, 1.0], "No executable configured");
     }
 
-    for i in 0..exe_count {
-        let is_running = exe_manager.is_running(&exe_manager.executables()[i].path);
-
-        let _id = ui.push_id(i as i32);
-
-        render_executable_item(
-            exe_manager,
+    let groups = exe_manager.groups().clone();
+    for group in &groups {
+        render_group_section(
             ui,
-            i,
-            is_running,
-            &mut to_launch,
-            &mut to_stop,
-            &mut to_remove,
+            exe_manager,
+            Some(&group.name),
+            group.launch_on_startup,
+            &mut actions,
         );
     }
+    render_group_section(ui, exe_manager, None, false, &mut actions);
+
+    render_add_group(ui, exe_manager);
+
+    // The modal is opened here so it shares the ID stack it is rendered with
+    if let Some(index) = actions.edit.take() {
+        open_edit_dialog(ui, exe_manager, index);
+    }
+    render_edit_dialog(ui, exe_manager);
 
     // Handle actions after the loop to avoid borrowing conflicts
-    handle_executable_actions(exe_manager, to_stop, to_launch, to_remove);
+    handle_executable_actions(exe_manager, actions);
+}
+
+/// Renders a collapsible group header with its controls and executables.
+/// `None` renders the ungrouped executables.
+fn render_group_section(
+    ui: &Ui,
+    exe_manager: &mut ExeManager,
+    group: Option<&str>,
+    group_launch_on_startup: bool,
+    actions: &mut PendingActions,
+) {
+    let indices: Vec<usize> = exe_manager
+        .executables()
+        .iter()
+        .enumerate()
+        .filter(|(_, exe)| exe.group.as_deref() == group)
+        .map(|(i, _)| i)
+        .collect();
+
+    // Hide the ungrouped section when every executable belongs to a group
+    if group.is_none() && indices.is_empty() && !exe_manager.groups().is_empty() {
+        return;
+    }
+
+    let label = format!(
+        "{} ({})###group_{}",
+        group.unwrap_or("Ungrouped"),
+        indices.len(),
+        group.unwrap_or("")
+    );
+    let is_open = CollapsingHeader::new(label.as_str())
+        .default_open(true)
+        .build(ui);
+
+    // Dropping an executable on a header moves it into that group
+    if let Some(target) = DragDropTarget::new(ui) {
+        if let Some(Ok(payload)) =
+            target.accept_payload::<usize, _>(EXE_PAYLOAD, DragDropFlags::empty())
+        {
+            actions.move_to_group = Some((payload.data, group.map(str::to_string)));
+        }
+        target.pop();
+    }
+
+    if !is_open {
+        return;
+    }
+
+    let _group_id = ui.push_id(label.as_str());
+
+    if ui.button("Launch all") {
+        actions.group = Some(GroupAction::LaunchAll(group.map(str::to_string)));
+    }
+    ui.same_line();
+    if ui.button("Stop all") {
+        actions.group = Some(GroupAction::StopAll(group.map(str::to_string)));
+    }
+
+    if let Some(name) = group {
+        ui.same_line();
+        let mut launch_on_startup = group_launch_on_startup;
+        if ui.checkbox("Launch group on startup", &mut launch_on_startup) {
+            actions.group = Some(GroupAction::SetLaunchOnStartup(
+                name.to_string(),
+                launch_on_startup,
+            ));
+        }
+        ui.same_line();
+        if ui.button("Delete group") {
+            actions.group = Some(GroupAction::Remove(name.to_string()));
+        }
+    }
+
+    for (position, &i) in indices.iter().enumerate() {
+        let state = exe_manager.state(&exe_manager.executables()[i].path);
+
+        let _id = ui.push_id(i as i32);
+
+        // Up/down buttons swap with the neighbouring entry of the same group
+        if ui.arrow_button("up", Direction::Up) && position > 0 {
+            actions.reorder = Some((i, indices[position - 1], group.map(str::to_string)));
+        }
+        ui.same_line();
+        if ui.arrow_button("down", Direction::Down) && position + 1 < indices.len() {
+            actions.reorder = Some((i, indices[position + 1], group.map(str::to_string)));
+        }
+        ui.same_line();
+
+        render_executable_item(exe_manager, ui, i, state, actions);
+    }
 }
 
 /// Renders a single executable item in the list
@@ -129,76 +658,854 @@ fn render_executable_item(
     exe_manager: &mut ExeManager,
     ui: &Ui,
     index: usize,
-    is_running: bool,
-    to_launch: &mut Option<String>,
-    to_stop: &mut Option<String>,
-    to_remove: &mut Option<usize>,
+    state: ExeState,
+    actions: &mut PendingActions,
 ) {
-    // Get the executable path and launch_on_startup flag at the given index
+    // Get the executable path, name and launch_on_startup flag at the given index
     let exe_path = exe_manager.executables()[index].path.clone();
-    let launch_on_startup_flag = exe_manager.executables()[index].launch_on_startup;
+    let display_name = shown_name(&exe_manager.executables()[index]);
+    let launch_on_startup_flag = exe_manager.launch_on_startup(&exe_manager.executables()[index]);
+    let is_enabled = exe_manager.is_enabled(&exe_manager.executables()[index]);
+    let is_url = exe_manager.executables()[index].kind == ExeKind::Url;
+    let tracks_program = exe_manager.executables()[index].kind.tracks_program();
 
-    // Status indicator
-    if is_running {
-        ui.text_colored([0.0, 1.0, 0.0, 1.0], "Running");
+    // Status indicator, links are never running
+    if is_url {
+        render_link_icon(ui);
     } else {
-        ui.text_colored([0.5, 0.5, 0.5, 1.0], "Not running");
+        match state {
+            ExeState::Running => ui.text_colored([0.0, 1.0, 0.0, 1.0], "Running"),
+            ExeState::Suspended => ui.text_colored([1.0, 0.8, 0.0, 1.0], "Suspended"),
+            ExeState::Stopped if !is_enabled => ui.text_colored([0.4, 0.4, 0.4, 1.0], "Disabled"),
+            ExeState::Stopped => ui.text_colored([0.5, 0.5, 0.5, 1.0], "Not running"),
+        }
+        match exe_manager.ready_state(&exe_path) {
+            Some(ReadyState::Starting) => {
+                ui.same_line();
+                ui.text_colored([1.0, 0.8, 0.0, 1.0], ReadyState::Starting.label());
+            }
+            Some(ReadyState::Failed) => {
+                ui.same_line();
+                ui.text_colored([1.0, 0.3, 0.3, 1.0], ReadyState::Failed.label());
+                if ui.is_item_hovered() {
+                    ui.tooltip_text("The readiness probe did not succeed within the timeout");
+                }
+            }
+            Some(ReadyState::Ready) | None => {}
+        }
+        if let Some(HealthStatus::Unhealthy { failures, error }) = exe_manager.health(&exe_path) {
+            ui.same_line();
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], "Unhealthy");
+            if ui.is_item_hovered() {
+                ui.tooltip_text(format!("{error} ({failures} failed checks in a row)"));
+            }
+        }
+        if state != ExeState::Stopped && tracks_program {
+            render_usage(ui, exe_manager, &exe_path);
+        }
     }
     ui.same_line();
 
-    // Executable path (truncated if too long)
-    let display_path = truncate_middle(&exe_path, 50);
+    // Executable name or path (truncated if too long)
+    let display_path = truncate_middle(&display_name, 50);
     ui.text_wrapped(&display_path);
+    if display_name != exe_path && ui.is_item_hovered() {
+        ui.tooltip_text(&exe_path);
+    }
+
+    // The path acts as the drag handle: dropping it on another entry moves it to that
+    // position, dropping it on a group header moves it into that group.
+    // Text has no ID, imgui derives one from its rectangle when null IDs are allowed.
+    if let Some(_tooltip) = DragDropSource::new(EXE_PAYLOAD)
+        .flags(DragDropFlags::SOURCE_ALLOW_NULL_ID)
+        .begin_payload(ui, index)
+    {
+        ui.text(&display_path);
+    }
+    if let Some(target) = DragDropTarget::new(ui) {
+        if let Some(Ok(payload)) =
+            target.accept_payload::<usize, _>(EXE_PAYLOAD, DragDropFlags::empty())
+        {
+            let group = exe_manager.executables()[index].group.clone();
+            actions.reorder = Some((payload.data, index, group));
+        }
+        target.pop();
+    }
+
+    // Next scheduled run
+    if let Some(next_run) = tick::next_scheduled_run(&exe_path) {
+        ui.same_line();
+        ui.text_disabled(format!("Next run: {}", format_next_run(next_run)));
+    }
 
     ui.same_line();
 
-    // We need to work with a mutable reference to the launch_on_startup flag
-    let mut launch_on_startup = launch_on_startup_flag;
-    if ui.checkbox("Launch on startup", &mut launch_on_startup) {
-        if let Err(e) = exe_manager.set_launch_on_startup(index, launch_on_startup) {
-            log::error!("Failed to update setting: {e}");
+    // Membership in the active profile
+    if exe_manager.active_profile().is_some() {
+        let mut enabled = is_enabled;
+        if ui.checkbox("Enabled", &mut enabled) {
+            if let Err(e) = exe_manager.set_enabled_in_profile(index, enabled) {
+                log::error!("Failed to update profile: {e}");
+            }
         }
+        ui.same_line();
     }
 
+    // We need to work with a mutable reference to the launch_on_startup flag.
+    // Executables disabled in the active profile never launch on startup.
+    let mut launch_on_startup = launch_on_startup_flag;
+    ui.disabled(!is_enabled, || {
+        if ui.checkbox("Launch on startup", &mut launch_on_startup) {
+            if let Err(e) = exe_manager.set_launch_on_startup(index, launch_on_startup) {
+                log::error!("Failed to update setting: {e}");
+            }
+        }
+    });
+
     ui.same_line();
 
-    // Launch/Stop button
-    if is_running {
+    // Launch/Stop and Suspend/Resume buttons
+    if is_url {
+        if ui.button("Open") {
+            actions.launch = Some(exe_path.clone());
+        }
+    } else if exe_manager.is_prompting(&exe_path) {
+        ui.text_disabled("Waiting for the UAC prompt");
+    } else if state == ExeState::Stopped {
+        if ui.button("Launch") {
+            actions.launch = Some(exe_path.clone());
+        }
+    } else if !tracks_program {
+        ui.text_disabled("Close it from Linux to stop it");
+    } else {
         if ui.button("Stop") {
-            *to_stop = Some(exe_path.clone());
+            actions.stop = Some(exe_path.clone());
         }
-    } else if ui.button("Launch") {
-        *to_launch = Some(exe_path.clone());
+        ui.same_line();
+        if state == ExeState::Suspended {
+            if ui.button("Resume") {
+                actions.resume = Some(exe_path.clone());
+            }
+        } else if ui.button("Suspend") {
+            actions.suspend = Some(exe_path.clone());
+        }
+    }
+
+    ui.same_line();
+
+    if ui.button("Edit") {
+        actions.edit = Some(index);
     }
 
     ui.same_line();
 
     // Remove button
     if ui.button("Remove") {
-        *to_remove = Some(index);
+        actions.remove = Some(index);
+    }
+}
+
+/// Renders the CPU, memory and uptime of a running executable, with the last minute of CPU usage
+fn render_usage(ui: &Ui, exe_manager: &ExeManager, path: &str) {
+    let uptime = exe_manager
+        .launched_at(path)
+        .and_then(|launched_at| launched_at.elapsed().ok())
+        .map(format_uptime);
+
+    let Some((sample, cpu_series)) = monitor::usage(path) else {
+        if let Some(uptime) = uptime {
+            ui.same_line();
+            ui.text_disabled(format!("up {uptime}"));
+        }
+        return;
+    };
+
+    ui.same_line();
+    ui.text_disabled(format!(
+        "CPU {:.1}% | {} | up {}",
+        sample.cpu_percent,
+        format_bytes(sample.working_set),
+        uptime.as_deref().unwrap_or("-")
+    ));
+    ui.same_line();
+    // Small peaks stay visible instead of being flattened against 100%
+    let scale_max = cpu_series.iter().copied().fold(10.0_f32, f32::max);
+    ui.plot_lines("##cpu_history", &cpu_series)
+        .graph_size([90.0, ui.text_line_height()])
+        .scale_min(0.0)
+        .scale_max(scale_max)
+        .build();
+    if ui.is_item_hovered() {
+        ui.tooltip_text(format!(
+            "CPU usage over the last minute, up to {scale_max:.0}%"
+        ));
     }
 }
 
+/// Renders the input used to create a new group
+fn render_add_group(ui: &Ui, exe_manager: &mut ExeManager) {
+    let Ok(mut name) = NEW_GROUP_NAME.lock() else {
+        return;
+    };
+
+    ui.input_text("##new_group_name", &mut name).build();
+    ui.same_line();
+    if ui.button("Add group") {
+        match exe_manager.add_group(&name) {
+            Ok(()) => name.clear(),
+            Err(e) => log::error!("Failed to add group: {e}"),
+        }
+    }
+}
+
+/// Fills the edit modal with the current values of an executable and opens it
+fn open_edit_dialog(ui: &Ui, exe_manager: &ExeManager, index: usize) {
+    let Some(exe) = exe_manager.executables().get(index) else {
+        return;
+    };
+    let Ok(mut state) = EDIT_STATE.lock() else {
+        return;
+    };
+
+    let env_text = exe
+        .env
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // The startup flag is edited as seen by the active profile, like the list checkbox
+    let mut draft = exe.clone();
+    draft.launch_on_startup = exe_manager.launch_on_startup(exe);
+
+    *state = Some(EditState {
+        index,
+        draft,
+        env_text,
+        map_ids_text: String::new(),
+        trigger_mode: 0,
+        error: None,
+    });
+    ui.open_popup(EDIT_POPUP);
+}
+
+/// Renders the edit modal, applying the draft through `ExeManager::update_exe` on save
+fn render_edit_dialog(ui: &Ui, exe_manager: &mut ExeManager) {
+    let Ok(mut guard) = EDIT_STATE.lock() else {
+        return;
+    };
+
+    let mut close = false;
+    PopupModal::new(EDIT_POPUP)
+        .always_auto_resize(true)
+        .build(ui, || {
+            let Some(state) = guard.as_mut() else {
+                ui.close_current_popup();
+                return;
+            };
+
+            ui.input_text("Name", &mut state.draft.name).build();
+            render_kind_combo(ui, &mut state.draft.kind);
+            ui.input_text("Path", &mut state.draft.path).build();
+            match state.draft.kind {
+                ExeKind::Windows => {
+                    ui.same_line();
+                    if ui.button("Browse...") {
+                        if let Some(selected_path) = open_file_dialog() {
+                            state.draft.path = selected_path;
+                        }
+                    }
+                }
+                ExeKind::LinuxNative => {
+                    ui.text_disabled("Absolute Unix path, e.g. /usr/bin/obs");
+                }
+                ExeKind::Url => {
+                    ui.text_disabled("Link with a scheme, e.g. https:// or steam://");
+                }
+            }
+            ui.input_text("Arguments", &mut state.draft.args).build();
+            ui.input_text("Working directory", &mut state.draft.working_dir)
+                .build();
+            ui.input_text_multiline("Environment", &mut state.env_text, [400.0, 80.0])
+                .build();
+            ui.text_disabled("One KEY=VALUE per line");
+            ui.checkbox("Launch on startup", &mut state.draft.launch_on_startup);
+            if state.draft.kind == ExeKind::Windows {
+                ui.checkbox("Run as administrator", &mut state.draft.elevated);
+                if state.draft.elevated {
+                    ui.text_disabled(
+                        "Asks for confirmation on every launch, the environment is not passed \
+                         and it is only launched on request or on startup",
+                    );
+                }
+            }
+            ui.input_text("Toggle keybind", &mut state.draft.keybind)
+                .hint("e.g. ALT+SHIFT+B")
+                .build();
+            ui.text_disabled("Default key combination, it can be changed in the Nexus keybinds");
+
+            ui.separator();
+            render_map_triggers_editor(ui, state);
+
+            ui.separator();
+            render_game_event_combo(ui, "Launch when", &mut state.draft.launch_on);
+            if state.draft.kind.tracks_program() {
+                render_game_event_combo(ui, "Stop when", &mut state.draft.stop_on);
+            }
+
+            ui.separator();
+            render_schedule_editor(ui, &mut state.draft.schedule);
+            render_focus_policy_combo(
+                ui,
+                &mut state.draft.focus_policy,
+                state.draft.kind.tracks_program(),
+            );
+
+            if state.draft.kind != ExeKind::Url {
+                if state.draft.kind.tracks_program() {
+                    ui.separator();
+                    render_priority_combo(ui, &mut state.draft.priority);
+                    render_affinity_editor(ui, &mut state.draft.affinity);
+
+                    ui.separator();
+                    render_limits_editor(ui, &mut state.draft.limits);
+                }
+
+                ui.separator();
+                render_health_check_editor(ui, &mut state.draft.health_check);
+
+                if state.draft.kind.tracks_program() {
+                    ui.separator();
+                    render_readiness_editor(ui, &mut state.draft.readiness);
+                }
+            }
+
+            if let Some(error) = &state.error {
+                ui.text_colored([1.0, 0.2, 0.2, 1.0], error);
+            }
+
+            ui.separator();
+
+            if ui.button("Save") {
+                let result = parse_env_lines(&state.env_text).and_then(|env| {
+                    let mut updated = state.draft.clone();
+                    updated.env = env;
+                    // Hidden settings of native Linux programs are dropped
+                    if !updated.kind.tracks_program() {
+                        updated.priority = Priority::Normal;
+                        updated.affinity = 0;
+                        updated.limits = None;
+                        updated.readiness = None;
+                        updated.stop_on = None;
+                        updated.map_triggers.stop_on_exit = false;
+                        updated.map_triggers.suspend_on_exit = false;
+                        updated.focus_policy = updated
+                            .focus_policy
+                            .filter(|policy| *policy == FocusPolicy::StartOnFocusGain);
+                    }
+                    exe_manager
+                        .update_exe(state.index, updated)
+                        .map_err(|e| e.to_string())
+                });
+                match result {
+                    Ok(()) => {
+                        if let Some(exe) = exe_manager.executables().get(state.index) {
+                            keybinds::register_exe_keybind(exe);
+                        }
+                        close = true;
+                    }
+                    Err(e) => state.error = Some(e),
+                }
+            }
+            ui.same_line();
+            if ui.button("Cancel") {
+                close = true;
+            }
+
+            if close {
+                ui.close_current_popup();
+            }
+        });
+
+    if close {
+        *guard = None;
+    }
+}
+
+/// Renders the map trigger rules of the executable being edited
+fn render_map_triggers_editor(ui: &Ui, state: &mut EditState) {
+    ui.text("Map triggers:");
+    ui.text_disabled("Launch when entering a matching map");
+
+    let triggers = &mut state.draft.map_triggers;
+    let mut to_remove = None;
+    for (i, condition) in triggers.conditions.iter().enumerate() {
+        let _id = ui.push_id(i as i32);
+        ui.bullet_text(condition.describe());
+        ui.same_line();
+        if ui.small_button("Remove") {
+            to_remove = Some(i);
+        }
+    }
+    if let Some(i) = to_remove {
+        triggers.conditions.remove(i);
+    }
+
+    let labels: Vec<&str> = GameMode::ALL.iter().map(GameMode::label).collect();
+    ui.combo_simple_string("##trigger_mode", &mut state.trigger_mode, &labels);
+    ui.same_line();
+    if ui.button("Add game mode") {
+        if let Some(&mode) = GameMode::ALL.get(state.trigger_mode) {
+            triggers
+                .conditions
+                .push(TriggerCondition::GameMode { mode });
+        }
+    }
+
+    ui.input_text("##trigger_map_ids", &mut state.map_ids_text)
+        .hint("Map ids, e.g. 1062, 1149")
+        .build();
+    ui.same_line();
+    if ui.button("Add maps") {
+        let ids: Result<Vec<u32>, _> = state
+            .map_ids_text
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::parse)
+            .collect();
+        match ids {
+            Ok(ids) if !ids.is_empty() => {
+                triggers.conditions.push(TriggerCondition::MapIds { ids });
+                state.map_ids_text.clear();
+            }
+            _ => state.error = Some(format!("Invalid map ids: {}", state.map_ids_text)),
+        }
+    }
+
+    if let Some(map) = tick::current_map() {
+        let mode = GameMode::from_map_type(map.map_type).map_or("other", |mode| mode.label());
+        ui.text_disabled(format!("Current map: {} ({mode})", map.map_id));
+        ui.same_line();
+        if ui.small_button("Use current map") {
+            state.map_ids_text = map.map_id.to_string();
+        }
+    }
+
+    // Only Linux can stop or suspend a native program
+    if state.draft.kind.tracks_program() {
+        ui.checkbox("Stop when leaving", &mut triggers.stop_on_exit);
+        ui.same_line();
+        ui.checkbox("Suspend when leaving", &mut triggers.suspend_on_exit);
+    }
+}
+
+/// Renders a combo selecting an optional game transition
+fn render_game_event_combo(ui: &Ui, label: &str, value: &mut Option<GameEvent>) {
+    const NEVER: &str = "Never";
+
+    ComboBox::new(label)
+        .preview_value(value.map_or(NEVER, |event| event.label()))
+        .build(ui, || {
+            if Selectable::new(NEVER).selected(value.is_none()).build(ui) {
+                *value = None;
+            }
+            for event in GameEvent::ALL {
+                if Selectable::new(event.label())
+                    .selected(*value == Some(event))
+                    .build(ui)
+                {
+                    *value = Some(event);
+                }
+            }
+        });
+}
+
+/// Renders the schedule of the executable being edited
+fn render_schedule_editor(ui: &Ui, schedule: &mut Option<Schedule>) {
+    const KINDS: [&str; 3] = ["Not scheduled", "Every N minutes", "Daily at"];
+
+    let mut kind = match schedule {
+        None => 0,
+        Some(Schedule::Every { .. }) => 1,
+        Some(Schedule::DailyAt { .. }) => 2,
+    };
+    if ui.combo_simple_string("Schedule", &mut kind, &KINDS) {
+        *schedule = match kind {
+            1 => Some(Schedule::Every { minutes: 30 }),
+            2 => Some(Schedule::DailyAt {
+                hour: 20,
+                minute: 0,
+            }),
+            _ => None,
+        };
+    }
+
+    match schedule {
+        Some(Schedule::Every { minutes }) => {
+            let mut value = *minutes as i32;
+            if ui.input_int("Minutes", &mut value).build() {
+                *minutes = value.max(1) as u32;
+            }
+        }
+        Some(Schedule::DailyAt { hour, minute }) => {
+            let mut value = [*hour as i32, *minute as i32];
+            if ui.input_int2("Hour, minute", &mut value).build() {
+                *hour = value[0].clamp(0, 23) as u32;
+                *minute = value[1].clamp(0, 59) as u32;
+            }
+        }
+        None => {}
+    }
+}
+
+/// Renders a combo selecting the type of the executable being edited,
+/// native Linux programs being only offered under wine
+fn render_kind_combo(ui: &Ui, kind: &mut ExeKind) {
+    ComboBox::new("Type")
+        .preview_value(kind.label())
+        .build(ui, || {
+            for value in ExeKind::ALL {
+                if value == ExeKind::LinuxNative && !wine::is_wine() && *kind != value {
+                    continue;
+                }
+                if Selectable::new(value.label())
+                    .selected(*kind == value)
+                    .build(ui)
+                {
+                    *kind = value;
+                }
+            }
+        });
+}
+
+/// Renders a combo selecting the priority of the executable being edited
+fn render_priority_combo(ui: &Ui, priority: &mut Priority) {
+    ComboBox::new("Priority")
+        .preview_value(priority.label())
+        .build(ui, || {
+            for value in Priority::ALL {
+                if Selectable::new(value.label())
+                    .selected(*priority == value)
+                    .build(ui)
+                {
+                    *priority = value;
+                }
+            }
+        });
+}
+
+/// Renders one checkbox per core of the CPU affinity of the executable being edited
+fn render_affinity_editor(ui: &Ui, affinity: &mut u64) {
+    const CORES_PER_ROW: usize = 8;
+    let core_count = process::core_count().min(64);
+
+    let mut all_cores = *affinity == 0;
+    if ui.checkbox("Run on all cores", &mut all_cores) {
+        *affinity = if all_cores {
+            0
+        } else {
+            process::all_cores_mask(core_count)
+        };
+    }
+    if all_cores {
+        return;
+    }
+
+    for core in 0..core_count {
+        if core % CORES_PER_ROW != 0 {
+            ui.same_line();
+        }
+        let bit = 1u64 << core;
+        let mut enabled = *affinity & bit != 0;
+        // The last core can't be unchecked, an empty mask means all cores
+        if ui.checkbox(format!("{core}##core_{core}"), &mut enabled) && *affinity != bit {
+            *affinity ^= bit;
+        }
+    }
+}
+
+/// Renders the resource limits of the executable being edited
+fn render_limits_editor(ui: &Ui, limits: &mut Option<ResourceLimits>) {
+    ui.text("Resource limits:");
+    ui.text_disabled("Checked every second while the executable runs");
+    let limits = limits.get_or_insert_with(ResourceLimits::default);
+
+    let mut has_memory_limit = limits.max_memory_mb.is_some();
+    if ui.checkbox("Memory limit", &mut has_memory_limit) {
+        limits.max_memory_mb = has_memory_limit.then_some(1024);
+    }
+    if let Some(max_memory_mb) = &mut limits.max_memory_mb {
+        ui.same_line();
+        let mut value = i32::try_from(*max_memory_mb).unwrap_or(i32::MAX);
+        if ui.input_int("MB##max_memory", &mut value).build() {
+            *max_memory_mb = u64::try_from(value.max(1)).unwrap_or(1);
+        }
+    }
+
+    let mut has_cpu_limit = limits.max_cpu_percent.is_some();
+    if ui.checkbox("CPU limit", &mut has_cpu_limit) {
+        limits.max_cpu_percent = has_cpu_limit.then_some(50.0);
+    }
+    if let Some(max_cpu_percent) = &mut limits.max_cpu_percent {
+        ui.same_line();
+        ui.input_float("%##max_cpu", max_cpu_percent).build();
+    }
+
+    if limits.is_empty() {
+        return;
+    }
+    let mut duration = i32::try_from(limits.duration_secs).unwrap_or(i32::MAX);
+    if ui.input_int("Exceeded for (s)", &mut duration).build() {
+        limits.duration_secs = u64::try_from(duration).unwrap_or(0).max(MIN_DURATION_SECS);
+    }
+    ComboBox::new("Action")
+        .preview_value(limits.action.label())
+        .build(ui, || {
+            for value in LimitAction::ALL {
+                if Selectable::new(value.label())
+                    .selected(limits.action == value)
+                    .build(ui)
+                {
+                    limits.action = value;
+                }
+            }
+        });
+}
+
+/// Renders the health check of the executable being edited
+fn render_health_check_editor(ui: &Ui, health_check: &mut Option<HealthCheck>) {
+    const PROBES: [&str; 4] = [
+        "None",
+        "TCP port open",
+        "HTTP 200 on localhost",
+        "File keeps being updated",
+    ];
+
+    ui.text("Health check:");
+    let mut selected = match health_check.as_ref().map(|check| &check.probe) {
+        None => 0,
+        Some(HealthProbe::Tcp { .. }) => 1,
+        Some(HealthProbe::Http { .. }) => 2,
+        Some(HealthProbe::FileUpdated { .. }) => 3,
+    };
+    if ui.combo_simple_string("Probe", &mut selected, &PROBES) {
+        let probe = match selected {
+            1 => Some(HealthProbe::Tcp { port: 8080 }),
+            2 => Some(HealthProbe::Http {
+                port: 8080,
+                path: "/".to_string(),
+            }),
+            3 => Some(HealthProbe::FileUpdated {
+                path: String::new(),
+                max_age_secs: 60,
+            }),
+            _ => None,
+        };
+        *health_check = probe.map(HealthCheck::new);
+    }
+    let Some(check) = health_check else {
+        return;
+    };
+
+    match &mut check.probe {
+        HealthProbe::Tcp { port } => input_port(ui, port),
+        HealthProbe::Http { port, path } => {
+            input_port(ui, port);
+            ui.input_text("URL path", path).hint("/health").build();
+        }
+        HealthProbe::FileUpdated { path, max_age_secs } => {
+            ui.input_text("File", path).build();
+            input_u64(ui, "Updated within (s)", max_age_secs, 1);
+        }
+    }
+    input_u64(ui, "Check every (s)", &mut check.interval_secs, 1);
+
+    let mut restart = check.restart_after.is_some();
+    if ui.checkbox("Restart when unhealthy", &mut restart) {
+        check.restart_after = restart.then_some(3);
+    }
+    if let Some(restart_after) = &mut check.restart_after {
+        let mut value = u64::from(*restart_after);
+        input_u64(ui, "Failed checks in a row", &mut value, 1);
+        *restart_after = u32::try_from(value).unwrap_or(u32::MAX);
+    }
+}
+
+/// Renders the readiness probe of the executable being edited
+fn render_readiness_editor(ui: &Ui, readiness: &mut Option<Readiness>) {
+    const PROBES: [&str; 4] = [
+        "Ready once spawned",
+        "Window shown",
+        "Port open",
+        "Log line",
+    ];
+
+    let _id = ui.push_id("readiness");
+    ui.text("Readiness:");
+    let mut selected = match readiness.as_ref().map(|readiness| &readiness.probe) {
+        None => 0,
+        Some(ReadinessProbe::Window) => 1,
+        Some(ReadinessProbe::Port { .. }) => 2,
+        Some(ReadinessProbe::LogLine { .. }) => 3,
+    };
+    if ui.combo_simple_string("Ready when", &mut selected, &PROBES) {
+        let probe = match selected {
+            1 => Some(ReadinessProbe::Window),
+            2 => Some(ReadinessProbe::Port { port: 8080 }),
+            3 => Some(ReadinessProbe::LogLine {
+                path: String::new(),
+                pattern: String::new(),
+            }),
+            _ => None,
+        };
+        *readiness = probe.map(Readiness::new);
+    }
+    let Some(readiness) = readiness else {
+        return;
+    };
+
+    match &mut readiness.probe {
+        ReadinessProbe::Window => {}
+        ReadinessProbe::Port { port } => input_port(ui, port),
+        ReadinessProbe::LogLine { path, pattern } => {
+            ui.input_text("Log file", path).build();
+            ui.input_text("Line regex", pattern)
+                .hint(r"Listening on \d+")
+                .build();
+        }
+    }
+    input_u64(ui, "Timeout (s)", &mut readiness.timeout_secs, 1);
+}
+
+/// Renders an integer input for a localhost port
+fn input_port(ui: &Ui, port: &mut u16) {
+    let mut value = i32::from(*port);
+    if ui.input_int("Port", &mut value).build() {
+        *port = u16::try_from(value.max(1)).unwrap_or(u16::MAX);
+    }
+}
+
+/// Renders an integer input for a positive value, clamped to `min`
+fn input_u64(ui: &Ui, label: &str, value: &mut u64, min: u64) {
+    let mut input = i32::try_from(*value).unwrap_or(i32::MAX);
+    if ui.input_int(label, &mut input).build() {
+        *value = u64::try_from(input).unwrap_or(0).max(min);
+    }
+}
+
+/// Renders a combo selecting the focus policy of the executable being edited, programs the
+/// runner can't stop or suspend can only be launched on focus gain
+fn render_focus_policy_combo(ui: &Ui, policy: &mut Option<FocusPolicy>, tracks_program: bool) {
+    const IGNORE: &str = "Ignore focus changes";
+
+    ComboBox::new("Game focus")
+        .preview_value(policy.map_or(IGNORE, |policy| policy.label()))
+        .build(ui, || {
+            if Selectable::new(IGNORE).selected(policy.is_none()).build(ui) {
+                *policy = None;
+            }
+            for value in FocusPolicy::ALL
+                .into_iter()
+                .filter(|value| tracks_program || *value == FocusPolicy::StartOnFocusGain)
+            {
+                if Selectable::new(value.label())
+                    .selected(*policy == Some(value))
+                    .build(ui)
+                {
+                    *policy = Some(value);
+                }
+            }
+        });
+}
+
+/// Formats a scheduled run as a time today, or with its date otherwise
+fn format_next_run(next_run: NaiveDateTime) -> String {
+    if next_run.date() == Local::now().date_naive() {
+        next_run.format("%H:%M").to_string()
+    } else {
+        next_run.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
+
+/// Parses `KEY=VALUE` lines into environment variables, ignoring blank lines
+fn parse_env_lines(text: &str) -> Result<BTreeMap<String, String>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.to_string()))
+            }
+            _ => Err(format!(
+                "Invalid environment line, expected KEY=VALUE: {line}"
+            )),
+        })
+        .collect()
+}
+
 /// Handles the actions collected during executable list rendering
-fn handle_executable_actions(
-    exe_manager: &mut ExeManager,
-    to_stop: Option<String>,
-    to_launch: Option<String>,
-    to_remove: Option<usize>,
-) {
-    if let Some(path) = to_stop {
+fn handle_executable_actions(exe_manager: &mut ExeManager, actions: PendingActions) {
+    if let Some(path) = actions.stop {
         if let Err(e) = exe_manager.stop_exe(&path) {
             log::error!("Failed to stop executable: {e}");
         }
     }
 
-    if let Some(path) = to_launch {
+    if let Some(path) = actions.launch {
         if let Err(e) = exe_manager.launch_exe(&path) {
             log::error!("Failed to launch executable: {e}");
         }
     }
 
-    if let Some(index) = to_remove {
+    if let Some(path) = actions.suspend {
+        if let Err(e) = exe_manager.suspend_exe(&path) {
+            log::error!("Failed to suspend executable: {e}");
+        }
+    }
+
+    if let Some(path) = actions.resume {
+        if let Err(e) = exe_manager.resume_exe(&path) {
+            log::error!("Failed to resume executable: {e}");
+        }
+    }
+
+    if let Some((index, group)) = actions.move_to_group {
+        if let Err(e) = exe_manager.set_exe_group(index, group) {
+            log::error!("Failed to move executable to group: {e}");
+        }
+    }
+
+    if let Some((from, to, group)) = actions.reorder {
+        let group_changed = exe_manager
+            .executables()
+            .get(from)
+            .is_some_and(|exe| exe.group != group);
+        let result = if group_changed {
+            exe_manager.set_exe_group(from, group)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result.and_then(|_| exe_manager.move_exe(from, to)) {
+            log::error!("Failed to reorder executable: {e}");
+        }
+    }
+
+    if let Some(action) = actions.group {
+        let result = match action {
+            GroupAction::LaunchAll(group) => exe_manager.launch_group(group.as_deref()),
+            GroupAction::StopAll(group) => exe_manager.stop_group(group.as_deref()),
+            GroupAction::SetLaunchOnStartup(name, value) => {
+                exe_manager.set_group_launch_on_startup(&name, value)
+            }
+            GroupAction::Remove(name) => exe_manager.remove_group(&name),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to apply group action: {e}");
+        }
+    }
+
+    if let Some(index) = actions.remove {
         if let Err(e) = exe_manager.remove_exe(index) {
             log::error!("Failed to remove executable: {e}");
         }
@@ -226,6 +1533,33 @@ pub fn toggle_window() {
     IS_WINDOW_OPEN.store(!IS_WINDOW_OPEN.load(Ordering::Relaxed), Ordering::Relaxed);
 }
 
+/// Draws a chain link icon, two overlapping rings the height of a text line
+fn render_link_icon(ui: &Ui) {
+    const COLOR: [f32; 4] = [0.4, 0.7, 1.0, 1.0];
+    let [x, y] = ui.cursor_screen_pos();
+    let height = ui.text_line_height();
+    let radius = height * 0.25;
+    let center_y = y + height * 0.5;
+
+    let draw_list = ui.get_window_draw_list();
+    for center_x in [x + radius + 1.0, x + radius * 2.5 + 1.0] {
+        draw_list
+            .add_circle([center_x, center_y], radius, COLOR)
+            .thickness(1.5)
+            .build();
+    }
+    ui.dummy([radius * 3.5 + 2.0, height]);
+}
+
+/// Returns the name to show for an executable, `Z:` drive paths being shown as Unix paths
+fn shown_name(exe: &Executable) -> String {
+    if exe.name.trim().is_empty() {
+        wine_path::display_path(&exe.path, wine::is_wine())
+    } else {
+        exe.name.clone()
+    }
+}
+
 /// Utility: truncate a long string keeping start and end with ellipsis in the middle
 fn truncate_middle(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
@@ -0,0 +1,206 @@
+/*!
+
+CPU and memory usage history of the running executables, and its formatting.
+
+The [monitor](crate::addon::monitor) samples the cumulated CPU time and working set of every
+process once per [SAMPLE_INTERVAL] and records them in a [UsageHistory], which turns CPU time
+deltas into percentages and keeps the last minute for the sparkline. Time is passed in so the
+history can be tested without waiting.
+
+*/
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Time between two samples of the same process
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of samples kept, one minute at the sample interval
+pub const HISTORY_LEN: usize = 60;
+
+/// Cumulated counters read from a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawUsage {
+    /// User and kernel time consumed since the process started
+    pub cpu_time: Duration,
+    /// Resident memory in bytes
+    pub working_set: u64,
+}
+
+/// Usage of a process over one sample interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    /// Share of the whole machine, 100% being every core busy
+    pub cpu_percent: f32,
+    pub working_set: u64,
+}
+
+/// Last minute of samples of one process
+#[derive(Debug, Clone, Default)]
+pub struct UsageHistory {
+    samples: VecDeque<Sample>,
+    previous: Option<(Instant, Duration)>,
+}
+
+impl UsageHistory {
+    /**
+     * Records the counters read at `now`. The first reading only sets the reference
+     * the next one is compared to, so no sample is added for it.
+     *
+     * # Arguments
+     * * `now` - When the counters were read
+     * * `usage` - Counters of the process
+     * * `core_count` - Number of logical cores, used to scale the CPU percentage
+     *
+     * # Returns
+     * The sample added to the history, if any.
+     */
+    pub fn record(&mut self, now: Instant, usage: RawUsage, core_count: usize) -> Option<Sample> {
+        let mut added = None;
+        if let Some((previous_at, previous_cpu)) = self.previous {
+            let elapsed = now.saturating_duration_since(previous_at);
+            let cpu = usage.cpu_time.saturating_sub(previous_cpu);
+            if !elapsed.is_zero() {
+                let cores = core_count.max(1) as f32;
+                let cpu_percent =
+                    (cpu.as_secs_f32() / elapsed.as_secs_f32() / cores * 100.0).clamp(0.0, 100.0);
+                if self.samples.len() == HISTORY_LEN {
+                    self.samples.pop_front();
+                }
+                let sample = Sample {
+                    cpu_percent,
+                    working_set: usage.working_set,
+                };
+                self.samples.push_back(sample);
+                added = Some(sample);
+            }
+        }
+        self.previous = Some((now, usage.cpu_time));
+        added
+    }
+
+    /// Returns the most recent sample
+    pub fn latest(&self) -> Option<Sample> {
+        self.samples.back().copied()
+    }
+
+    /// Returns the CPU percentages, oldest first
+    pub fn cpu_series(&self) -> Vec<f32> {
+        self.samples
+            .iter()
+            .map(|sample| sample.cpu_percent)
+            .collect()
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `145.2 MB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Formats an uptime with its two most significant units, e.g. `2h 05m` or `42s`
+pub fn format_uptime(uptime: Duration) -> String {
+    let seconds = uptime.as_secs();
+    let (days, hours, minutes) = (seconds / 86_400, seconds / 3600 % 24, seconds / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {:02}s", seconds % 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(cpu_millis: u64, working_set: u64) -> RawUsage {
+        RawUsage {
+            cpu_time: Duration::from_millis(cpu_millis),
+            working_set,
+        }
+    }
+
+    #[test]
+    fn cpu_percent_is_relative_to_every_core() {
+        let start = Instant::now();
+        let mut history = UsageHistory::default();
+
+        assert_eq!(history.record(start, usage(1000, 10), 4), None);
+        assert_eq!(history.latest(), None);
+
+        // Two cores busy out of four
+        let sample = history.record(start + Duration::from_secs(1), usage(3000, 20), 4);
+        assert_eq!(
+            sample,
+            Some(Sample {
+                cpu_percent: 50.0,
+                working_set: 20
+            })
+        );
+        assert_eq!(history.latest(), sample);
+    }
+
+    #[test]
+    fn history_keeps_the_last_minute() {
+        let start = Instant::now();
+        let mut history = UsageHistory::default();
+        for second in 0..=HISTORY_LEN as u64 + 10 {
+            history.record(
+                start + Duration::from_secs(second),
+                usage(second * 100, second),
+                1,
+            );
+        }
+
+        let series = history.cpu_series();
+        assert_eq!(series.len(), HISTORY_LEN);
+        assert!(series.iter().all(|&percent| (percent - 10.0).abs() < 0.01));
+        assert_eq!(history.latest().map(|sample| sample.working_set), Some(70));
+    }
+
+    #[test]
+    fn counter_resets_are_clamped() {
+        let start = Instant::now();
+        let mut history = UsageHistory::default();
+        history.record(start, usage(5000, 0), 1);
+        history.record(start + Duration::from_secs(1), usage(1000, 0), 1);
+        assert_eq!(history.latest().map(|sample| sample.cpu_percent), Some(0.0));
+
+        // Two readings at the same instant add nothing
+        history.record(start + Duration::from_secs(1), usage(2000, 0), 1);
+        assert_eq!(history.cpu_series().len(), 1);
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(150 * 1024 * 1024), "150.0 MB");
+        assert_eq!(format_bytes(1536 * 1024 * 1024), "1.5 GB");
+        assert_eq!(format_uptime(Duration::from_secs(42)), "42s");
+        assert_eq!(format_uptime(Duration::from_secs(125)), "2m 05s");
+        assert_eq!(
+            format_uptime(Duration::from_secs(2 * 3600 + 5 * 60)),
+            "2h 05m"
+        );
+        assert_eq!(
+            format_uptime(Duration::from_secs(3 * 86_400 + 3600)),
+            "3d 01h"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+/*!
+
+Detection of wine, and launching of native Linux programs through its `start.exe /unix` bridge.
+
+Wine exports `wine_get_version` from its `ntdll.dll`, real Windows does not: its presence is
+how we know the game runs under wine (or Proton). The result is cached on first use.
+
+*/
+
+use std::{process::Command, sync::OnceLock};
+
+use crate::addon::wine_path;
+
+static IS_WINE: OnceLock<bool> = OnceLock::new();
+
+/// Checks whether the game is running under wine
+pub fn is_wine() -> bool {
+    *IS_WINE.get_or_init(|| {
+        let is_wine = imp::has_wine_exports();
+        log::info!("Running under {}", if is_wine { "wine" } else { "Windows" });
+        is_wine
+    })
+}
+
+/**
+ * Creates the command launching a native Linux program through `start.exe /unix`.
+ * `/wait` keeps `start.exe` alive until the program exits, so its exit can be tracked.
+ * Ending `start.exe` does not end the program though, the runner never stops it.
+ *
+ * # Arguments
+ * * `unix_path` - Absolute Unix path of the program
+ */
+pub fn unix_command(unix_path: &str) -> Command {
+    let mut command = Command::new("start.exe");
+    command.args(["/wait", "/unix"]).arg(unix_path);
+    command
+}
+
+/// Unix home directory of the user, from `HOME` or the `WINEHOMEDIR` set by wine
+pub fn unix_home() -> Option<String> {
+    wine_path::unix_home(
+        std::env::var("HOME").ok().as_deref(),
+        std::env::var("WINEHOMEDIR").ok().as_deref(),
+    )
+}
+
+/// Version reported by wine's `wine_get_version`, e.g. `9.0`
+pub fn version() -> Option<String> {
+    imp::version()
+}
+
+/// Host system name and release reported by wine's `wine_get_host_version`, e.g. `Linux`
+pub fn host_version() -> Option<(String, String)> {
+    imp::host_version()
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::ffi::{CStr, c_char, c_void};
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetModuleHandleA(module_name: *const c_char) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, proc_name: *const c_char) -> *mut c_void;
+    }
+
+    type WineGetVersion = unsafe extern "C" fn() -> *const c_char;
+    type WineGetHostVersion = unsafe extern "C" fn(*mut *const c_char, *mut *const c_char);
+
+    /// Returns the address of an ntdll export
+    fn ntdll_export(name: &CStr) -> Option<*mut c_void> {
+        // SAFETY: both names are nul terminated, ntdll is loaded in every process
+        unsafe {
+            let ntdll = GetModuleHandleA(c"ntdll.dll".as_ptr());
+            if ntdll.is_null() {
+                return None;
+            }
+            let export = GetProcAddress(ntdll, name.as_ptr());
+            (!export.is_null()).then_some(export)
+        }
+    }
+
+    /// Copies a C string owned by wine
+    fn to_string(value: *const c_char) -> Option<String> {
+        // SAFETY: wine returns static nul terminated strings
+        (!value.is_null()).then(|| {
+            unsafe { CStr::from_ptr(value) }
+                .to_string_lossy()
+                .into_owned()
+        })
+    }
+
+    pub fn has_wine_exports() -> bool {
+        ntdll_export(c"wine_get_version").is_some()
+    }
+
+    pub fn version() -> Option<String> {
+        let export = ntdll_export(c"wine_get_version")?;
+        // SAFETY: the export has this signature in every wine version
+        let wine_get_version =
+            unsafe { std::mem::transmute::<*mut c_void, WineGetVersion>(export) };
+        to_string(unsafe { wine_get_version() })
+    }
+
+    pub fn host_version() -> Option<(String, String)> {
+        let export = ntdll_export(c"wine_get_host_version")?;
+        // SAFETY: the export has this signature in every wine version
+        let wine_get_host_version =
+            unsafe { std::mem::transmute::<*mut c_void, WineGetHostVersion>(export) };
+        let mut sysname = std::ptr::null();
+        let mut release = std::ptr::null();
+        unsafe { wine_get_host_version(&mut sysname, &mut release) };
+        Some((to_string(sysname)?, to_string(release).unwrap_or_default()))
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    pub fn has_wine_exports() -> bool {
+        false
+    }
+
+    pub fn version() -> Option<String> {
+        None
+    }
+
+    pub fn host_version() -> Option<(String, String)> {
+        None
+    }
+}
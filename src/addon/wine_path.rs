@@ -0,0 +1,228 @@
+/*!
+
+Translation between Unix paths and the paths wine programs see.
+
+Wine maps the Unix root directory to the `Z:` drive by default, so `/home/deck/tool.exe`
+is `Z:\home\deck\tool.exe` from the game. Paths typed or pasted by the user are normalized
+with [normalize] before being validated, and `Z:` paths are shown as Unix paths with
+[display_path] since that's how Linux users know them.
+
+The functions are pure: the home directory is passed by the caller.
+
+*/
+
+/// Drive wine maps the Unix root directory to
+pub const UNIX_ROOT_DRIVE: char = 'Z';
+
+/**
+ * Normalizes a path typed by the user into a Windows path.
+ * Surrounding whitespace and quotes are removed, `~` is expanded with `home`,
+ * Unix paths are mapped to the `Z:` drive and `/` separators are replaced with `\`.
+ *
+ * # Arguments
+ * * `path` - Path as typed by the user
+ * * `home` - Unix home directory used to expand `~`
+ */
+pub fn normalize(path: &str, home: Option<&str>) -> String {
+    let path = trim_path(path);
+
+    if let Some(unix_path) = expand_home(path, home).filter(|path| path.starts_with('/')) {
+        return to_wine_path(&unix_path);
+    }
+    if path.starts_with('/') && !path.starts_with("//") {
+        return to_wine_path(path);
+    }
+    path.replace('/', "\\")
+}
+
+/**
+ * Normalizes a path typed by the user into a Unix path, for native Linux programs.
+ * `~` is expanded with `home` and `Z:` drive paths are mapped back to Unix paths.
+ * Returns the trimmed path unchanged if it can't be translated.
+ */
+pub fn normalize_unix(path: &str, home: Option<&str>) -> String {
+    let path = trim_path(path);
+    expand_home(path, home)
+        .or_else(|| to_unix_path(path))
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Maps an absolute Unix path to the `Z:` drive
+pub fn to_wine_path(unix_path: &str) -> String {
+    let mut wine_path = format!("{UNIX_ROOT_DRIVE}:");
+    let mut previous_separator = false;
+    for c in unix_path.chars() {
+        // Collapse repeated separators
+        if c == '/' {
+            if !previous_separator {
+                wine_path.push('\\');
+            }
+            previous_separator = true;
+        } else {
+            wine_path.push(c);
+            previous_separator = false;
+        }
+    }
+    wine_path
+}
+
+/// Maps a `Z:` drive path back to a Unix path, `None` for other paths
+pub fn to_unix_path(wine_path: &str) -> Option<String> {
+    let mut chars = wine_path.chars();
+    let drive = chars.next()?;
+    if !drive.eq_ignore_ascii_case(&UNIX_ROOT_DRIVE) || chars.next() != Some(':') {
+        return None;
+    }
+
+    let rest = chars.as_str().replace('\\', "/");
+    if rest.is_empty() {
+        return Some("/".to_string());
+    }
+    if !rest.starts_with('/') {
+        return None;
+    }
+    Some(rest)
+}
+
+/// Returns the path to show to the user: the Unix path for `Z:` paths under wine
+pub fn display_path(path: &str, is_wine: bool) -> String {
+    is_wine
+        .then(|| to_unix_path(path))
+        .flatten()
+        .unwrap_or_else(|| path.to_string())
+}
+
+/**
+ * Returns the Unix home directory from the environment variables, either `HOME`
+ * or the `WINEHOMEDIR` set by wine (e.g. `\??\Z:\home\deck`).
+ */
+pub fn unix_home(home: Option<&str>, wine_home_dir: Option<&str>) -> Option<String> {
+    home.filter(|home| home.starts_with('/'))
+        .map(|home| home.to_string())
+        .or_else(|| {
+            let wine_home_dir = wine_home_dir?;
+            to_unix_path(wine_home_dir.trim_start_matches(r"\??\"))
+        })
+}
+
+fn trim_path(path: &str) -> &str {
+    path.trim().trim_matches(|c| c == '"' || c == '\'').trim()
+}
+
+/// Expands a leading `~`, `None` if the path does not start with it or there is no home
+fn expand_home(path: &str, home: Option<&str>) -> Option<String> {
+    let rest = path.strip_prefix('~')?;
+    if !rest.is_empty() && !rest.starts_with(['/', '\\']) {
+        // `~user` forms are not supported
+        return None;
+    }
+    let home = home?.trim_end_matches('/');
+    Some(format!("{home}{}", rest.replace('\\', "/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOME: Option<&str> = Some("/home/deck");
+
+    #[test]
+    fn unix_paths_are_mapped_to_the_z_drive() {
+        assert_eq!(
+            normalize("/home/deck/Blish HUD/Blish HUD.exe", HOME),
+            r"Z:\home\deck\Blish HUD\Blish HUD.exe"
+        );
+        assert_eq!(normalize("/opt//tools/", HOME), r"Z:\opt\tools\");
+    }
+
+    #[test]
+    fn home_is_expanded() {
+        assert_eq!(
+            normalize("~/tools/taco.exe", HOME),
+            r"Z:\home\deck\tools\taco.exe"
+        );
+        assert_eq!(normalize("~", HOME), r"Z:\home\deck");
+        assert_eq!(normalize("~/taco.exe", None), r"~\taco.exe");
+        assert_eq!(normalize("~other/taco.exe", HOME), r"~other\taco.exe");
+    }
+
+    #[test]
+    fn windows_paths_accept_both_separators() {
+        assert_eq!(normalize("C:/Games/tool.exe", HOME), r"C:\Games\tool.exe");
+        assert_eq!(normalize(r"C:\Games\tool.exe", HOME), r"C:\Games\tool.exe");
+        assert_eq!(
+            normalize(r"\\server\share\tool.exe", HOME),
+            r"\\server\share\tool.exe"
+        );
+        assert_eq!(
+            normalize("//server/share/tool.exe", HOME),
+            r"\\server\share\tool.exe"
+        );
+    }
+
+    #[test]
+    fn quotes_and_whitespace_are_trimmed() {
+        assert_eq!(
+            normalize("  \"/home/deck/my tool.exe\" ", HOME),
+            r"Z:\home\deck\my tool.exe"
+        );
+        assert_eq!(normalize("'C:\\tool.exe'", HOME), r"C:\tool.exe");
+    }
+
+    #[test]
+    fn z_drive_paths_are_mapped_back() {
+        assert_eq!(
+            to_unix_path(r"Z:\home\deck\tool.exe").as_deref(),
+            Some("/home/deck/tool.exe")
+        );
+        assert_eq!(to_unix_path("z:/usr/bin").as_deref(), Some("/usr/bin"));
+        assert_eq!(to_unix_path("Z:").as_deref(), Some("/"));
+        assert_eq!(to_unix_path(r"C:\Games"), None);
+        assert_eq!(to_unix_path("Z:relative"), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        for unix_path in ["/", "/usr/bin/obs", "/home/deck/with space/a.exe"] {
+            assert_eq!(
+                to_unix_path(&to_wine_path(unix_path)).as_deref(),
+                Some(unix_path)
+            );
+        }
+    }
+
+    #[test]
+    fn native_paths_are_normalized_to_unix() {
+        assert_eq!(normalize_unix(r"Z:\usr\bin\obs", HOME), "/usr/bin/obs");
+        assert_eq!(
+            normalize_unix("~/bin/overlay", HOME),
+            "/home/deck/bin/overlay"
+        );
+        assert_eq!(normalize_unix(" /usr/bin/obs ", HOME), "/usr/bin/obs");
+        assert_eq!(normalize_unix(r"C:\tool.exe", HOME), r"C:\tool.exe");
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(display_path(r"Z:\opt\tool.exe", true), "/opt/tool.exe");
+        assert_eq!(display_path(r"Z:\opt\tool.exe", false), r"Z:\opt\tool.exe");
+        assert_eq!(display_path(r"C:\tool.exe", true), r"C:\tool.exe");
+    }
+
+    #[test]
+    fn home_directory() {
+        assert_eq!(
+            unix_home(Some("/home/deck"), None).as_deref(),
+            Some("/home/deck")
+        );
+        assert_eq!(
+            unix_home(None, Some(r"\??\Z:\home\deck")).as_deref(),
+            Some("/home/deck")
+        );
+        assert_eq!(
+            unix_home(Some(r"C:\users\deck"), Some(r"\??\Z:\home\deck")).as_deref(),
+            Some("/home/deck")
+        );
+        assert_eq!(unix_home(None, None), None);
+    }
+}